@@ -44,7 +44,7 @@ pub fn part_two(input: &str) -> Option<u64> {
 
     let answer: u64 = first_column
         .iter()
-        .map(|k| k * (*map.entry(*k).or_default() as u64))
+        .map(|k| k * *map.entry(*k).or_default())
         .sum();
 
     Some(answer)
@@ -80,7 +80,7 @@ impl WordSearchGrid {
 
     fn transpose(&self) -> Self {
         // assert!(!self.grid.is_empty());
-        if self.grid.len() == 0 {
+        if self.grid.is_empty() {
             return WordSearchGrid { grid: vec![] };
         }
         let len = self.grid[0].len();
@@ -97,7 +97,7 @@ impl WordSearchGrid {
     }
 
     fn diagonals_45(&self) -> Self {
-        if self.grid.len() == 0 {
+        if self.grid.is_empty() {
             return WordSearchGrid { grid: vec![] };
         }
         let diagonals_number = self.grid[0].len() * 2 - 1;
@@ -126,7 +126,7 @@ impl WordSearchGrid {
     }
 
     fn diagonals_135(&self) -> Self {
-        if self.grid.len() == 0 {
+        if self.grid.is_empty() {
             return WordSearchGrid { grid: vec![] };
         }
         let diagonals_number = self.grid[0].len() * 2 - 1;
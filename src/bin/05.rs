@@ -34,11 +34,11 @@ impl<T> State<T>
 where
     T: Eq + std::hash::Hash,
 {
-    pub fn get_dependents(self: &Self, dependency: &T) -> Option<&HashSet<T>> {
+    pub fn get_dependents(&self, dependency: &T) -> Option<&HashSet<T>> {
         self.dependents.get(dependency)
     }
 
-    pub fn is_resolved(self: &Self) -> bool {
+    pub fn is_resolved(&self) -> bool {
         self.depends_on.is_empty()
     }
 }
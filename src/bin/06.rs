@@ -70,7 +70,7 @@ impl Grid {
         let mut guard_position = self.find_guard().unwrap();
         let mut visited: HashSet<Point> = HashSet::new();
 
-        let directions = vec![
+        let directions = [
             Direction::Up,
             Direction::Right,
             Direction::Down,
@@ -106,7 +106,7 @@ impl Grid {
         let mut guard_position = self.find_guard().unwrap();
         let mut visited: HashMap<Point, Vec<Direction>> = HashMap::new();
 
-        let directions = vec![
+        let directions = [
             Direction::Up,
             Direction::Right,
             Direction::Down,
@@ -150,21 +150,21 @@ impl Grid {
     ) -> (Vec<Point>, bool) {
         let mut visited_points = vec![];
         let mut left_grid = false;
-        let mut curr_point = point.clone();
+        let mut curr_point = *point;
         let mut found_obstacle = false;
 
         while !left_grid && !found_obstacle {
             if let Some(pt) = curr_point.muv(self, &direction) {
                 if self.get_block(&pt) == &Block::Obstacle {
                     found_obstacle = true;
-                    visited_points.push(curr_point.clone());
+                    visited_points.push(curr_point);
                 } else {
-                    visited_points.push(curr_point.clone());
-                    curr_point = pt.clone();
+                    visited_points.push(curr_point);
+                    curr_point = pt;
                 }
             } else {
                 left_grid = true;
-                visited_points.push(curr_point.clone());
+                visited_points.push(curr_point);
             }
         }
 
@@ -47,7 +47,7 @@ fn parse_input(input: &str) -> HashMap<u64, Vec<u64>> {
 
 fn check_results(result: u64, operands: Vec<u64>, operations: Vec<Operation>) -> bool {
     let results: Vec<u64> = operands.iter().fold(vec![], |acc, &x| {
-        if acc.len() == 0 {
+        if acc.is_empty() {
             return vec![x];
         }
         operations
@@ -65,7 +65,7 @@ fn check_results(result: u64, operands: Vec<u64>, operations: Vec<Operation>) ->
             .collect()
     });
 
-    results.contains(&&result)
+    results.contains(&result)
 }
 
 fn sum_valid_results(functions: HashMap<u64, Vec<u64>>, operations: Vec<Operation>) -> u64 {
@@ -68,8 +68,8 @@ fn generate_multiple_antinodes(
     width: usize,
     height: usize,
 ) -> HashSet<Point> {
-    let delta_x: usize = (a2.0 - a1.0).abs() as usize;
-    let delta_y: usize = (a2.1 - a1.1).abs() as usize;
+    let delta_x: usize = (a2.0 - a1.0).unsigned_abs();
+    let delta_y: usize = (a2.1 - a1.1).unsigned_abs();
 
     let gcd = gcd(delta_x, delta_y);
 
@@ -77,20 +77,20 @@ fn generate_multiple_antinodes(
     let dy = (a2.1 - a1.1) / (gcd as isize);
 
     let mut antinodes = HashSet::new();
-    let mut new_pt = a1.clone();
+    let mut new_pt = a1;
 
     while is_in_grid(new_pt, width, height) {
-        antinodes.insert(new_pt.clone());
-        new_pt.0 = new_pt.0 + dx;
-        new_pt.1 = new_pt.1 + dy;
+        antinodes.insert(new_pt);
+        new_pt.0 += dx;
+        new_pt.1 += dy;
     }
 
-    let mut new_pt = a2.clone();
+    let mut new_pt = a2;
 
     while is_in_grid(new_pt, width, height) {
-        antinodes.insert(new_pt.clone());
-        new_pt.0 = new_pt.0 - dx;
-        new_pt.1 = new_pt.1 - dy;
+        antinodes.insert(new_pt);
+        new_pt.0 -= dx;
+        new_pt.1 -= dy;
     }
 
     antinodes
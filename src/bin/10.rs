@@ -42,10 +42,11 @@ fn find_points_next(pt: Point, value: u32, grid: &Grid) -> Vec<Point> {
             let new_x = new_x as usize;
             let new_y = new_y as usize;
 
-            if new_x < grid.positions[0].len() && new_y < grid.positions.len() {
-                if grid.positions[new_y][new_x] == value {
-                    next_points.push(Point(new_x, new_y));
-                }
+            if new_x < grid.positions[0].len()
+                && new_y < grid.positions.len()
+                && grid.positions[new_y][new_x] == value
+            {
+                next_points.push(Point(new_x, new_y));
             }
         }
     }
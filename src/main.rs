@@ -1,144 +1,612 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{parse, AppArguments};
+use advent_of_code::template::commands::{
+    all, doctor, download, inputs, read, scaffold, session, solve, stars, status, time,
+};
+use advent_of_code::template::{terminal, Error};
+use args::{parse, print_help, AppArguments};
+use std::process;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::{inputs, session};
+    use advent_of_code::template::terminal::ColorChoice;
+    use advent_of_code::template::{Day, DaySet, Part};
+    use pico_args::Arguments;
+
+    /// Describes a subcommand for `--help`.
+    pub struct Command {
+        pub name: &'static str,
+        /// Positional arguments, e.g. `<day>`.
+        pub args: &'static str,
+        pub about: &'static str,
+        /// Flags and their description, e.g. `("--submit <part>", "...")`.
+        pub flags: &'static [(&'static str, &'static str)],
+    }
+
+    pub const COMMANDS: &[Command] = &[
+        Command {
+            name: "scaffold",
+            args: "<day>",
+            about: "Create the solution, input and example files of a day.",
+            flags: &[
+                ("--download", "Download input and puzzle description first."),
+                ("--overwrite", "Replace an existing solution file."),
+                ("--template <name>", "Use a named template from aoc.toml."),
+                (
+                    "--dry-run",
+                    "Print what would be created without writing files.",
+                ),
+            ],
+        },
+        Command {
+            name: "download",
+            args: "<day|days>",
+            about: "Download the input and puzzle description of a day, or the missing ones of several days.",
+            flags: &[("--all", "Download all released days that are missing.")],
+        },
+        Command {
+            name: "inputs",
+            args: "<encrypt|decrypt> [<days>]",
+            about: "Encrypt inputs so that they can be committed, or decrypt them after cloning.",
+            flags: &[(
+                "--overwrite",
+                "Replace inputs that differ from their encrypted copy.",
+            )],
+        },
+        Command {
+            name: "read",
+            args: "<day>",
+            about: "Read the downloaded puzzle description of a day.",
+            flags: &[("--part <part>", "Only show the description of part 1 or 2.")],
+        },
+        Command {
+            name: "solve",
+            args: "<day>",
+            about: "Run the solution of a day against its input.",
+            flags: &[
+                ("--release", "Run an optimized build."),
+                ("--dhat", "Profile heap allocations with dhat."),
+                ("--submit <part>", "Submit the answer of part 1 or 2."),
+                ("--json", "Print the results as JSON."),
+            ],
+        },
+        Command {
+            name: "all",
+            args: "[<days>]",
+            about: "Run the solutions of all days, or of a selection like `1..=10`, `odd` or `unsolved`.",
+            flags: &[
+                ("--release", "Run optimized builds."),
+                ("--json", "Print the results as JSON."),
+            ],
+        },
+        Command {
+            name: "time",
+            args: "[<days>]",
+            about: "Benchmark solutions. Without days, all days that are not fully benchmarked.",
+            flags: &[
+                (
+                    "--all",
+                    "Benchmark all days, even if they have been benchmarked.",
+                ),
+                ("--store", "Store the timings and update the readme."),
+                ("--memory", "Also measure the heap usage of each part."),
+                ("--json", "Print the results as JSON."),
+            ],
+        },
+        Command {
+            name: "stars",
+            args: "",
+            about: "Update the progress calendar in the readme.",
+            flags: &[],
+        },
+        Command {
+            name: "status",
+            args: "",
+            about: "Show the files, benchmarks and answers of every day, and what is missing.",
+            flags: &[("--json", "Print the status as JSON.")],
+        },
+        Command {
+            name: "session",
+            args: "<set|show|check> [<token>]",
+            about: "Store, show or check the session cookie aoc-cli uses to download and submit.",
+            flags: &[],
+        },
+        Command {
+            name: "doctor",
+            args: "",
+            about: "Check that aoc-cli, the session cookie, the data directories and the readme are set up.",
+            flags: &[],
+        },
+        Command {
+            name: "today",
+            args: "",
+            about: "Scaffold, download and read the puzzle of the current day.",
+            flags: &[("--wait", "Wait for the next puzzle to unlock first.")],
+        },
+    ];
 
     pub enum AppArguments {
         Download {
             day: Day,
         },
+        DownloadAll {
+            days: Option<DaySet>,
+        },
+        Inputs {
+            action: inputs::Action,
+            days: Option<DaySet>,
+            overwrite: bool,
+        },
         Read {
             day: Day,
+            part: Option<Part>,
         },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            dry_run: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            submit: Option<Part>,
+            json: bool,
         },
         All {
+            days: Option<DaySet>,
             release: bool,
+            json: bool,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<DaySet>,
             store: bool,
+            memory: bool,
+            json: bool,
+        },
+        Stars,
+        Status {
+            json: bool,
+        },
+        Session {
+            action: session::Action,
+            token: Option<String>,
         },
+        Doctor,
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
+        /// Print the usage of all commands, or of a single command.
+        Help(Option<&'static Command>),
+    }
+
+    /// Parses the command and the global `--color` option, which is accepted by all commands.
+    pub fn parse() -> Result<(AppArguments, ColorChoice), Box<dyn std::error::Error>> {
+        let mut args = Arguments::from_env();
+        let color = args.opt_value_from_str("--color")?.unwrap_or_default();
+        Ok((parse_command(args)?, color))
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    fn parse_command(mut args: Arguments) -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let help = args.contains(["-h", "--help"]);
+
+        let Some(name) = args.subcommand()? else {
+            if help {
+                return Ok(AppArguments::Help(None));
+            }
+            return Err("no command specified. Run with `--help` to list all commands.".into());
+        };
+
+        if name == "help" {
+            let topic: Option<String> = args.opt_free_from_str()?;
+            return Ok(AppArguments::Help(
+                topic.as_deref().map(find_command).transpose()?,
+            ));
+        }
+
+        let command = find_command(&name)?;
+
+        if help {
+            return Ok(AppArguments::Help(Some(command)));
+        }
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
+        // flags are parsed first, the remaining arguments have to be positional.
+        let app_args = match name.as_str() {
+            "all" => {
+                let release = args.contains("--release");
+                let json = args.contains("--json");
+                let free = finish(args, command, 1)?;
+                AppArguments::All {
+                    days: free.first().map(|x| parse_days(x)).transpose()?,
+                    release,
+                    json,
+                }
+            }
+            "time" => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let memory = args.contains("--memory");
+                let json = args.contains("--json");
+                let free = finish(args, command, 1)?;
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    days: free.first().map(|x| parse_days(x)).transpose()?,
                     store,
+                    memory,
+                    json,
+                }
+            }
+            "download" => {
+                let all = args.contains("--all");
+                let free = finish(args, command, 1)?;
+
+                // a single day is always downloaded, other selections only download what is missing.
+                match free.first() {
+                    None if all => AppArguments::DownloadAll { days: None },
+                    Some(x) if !all && parse_day(x).is_ok() => {
+                        AppArguments::Download { day: parse_day(x)? }
+                    }
+                    Some(x) if !all => AppArguments::DownloadAll {
+                        days: Some(parse_days(x)?),
+                    },
+                    Some(_) => {
+                        return Err(format!(
+                            "`--all` can't be combined with days. Usage: {}",
+                            usage(command)
+                        )
+                        .into())
+                    }
+                    None => {
+                        required_day(&free, command).map(|day| AppArguments::Download { day })?
+                    }
+                }
+            }
+            "inputs" => {
+                let overwrite = args.contains("--overwrite");
+                let free = finish(args, command, 2)?;
+                let action = free.first().ok_or_else(|| {
+                    format!(
+                        "missing argument <encrypt|decrypt>. Usage: {}",
+                        usage(command)
+                    )
+                })?;
+
+                AppArguments::Inputs {
+                    action: action.parse()?,
+                    days: free.get(1).map(|x| parse_days(x)).transpose()?,
+                    overwrite,
+                }
+            }
+            "read" => {
+                let part = args.opt_value_from_str("--part")?;
+                let free = finish(args, command, 1)?;
+                AppArguments::Read {
+                    day: required_day(&free, command)?,
+                    part,
+                }
+            }
+            "scaffold" => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let template = args.opt_value_from_str("--template")?;
+                let dry_run = args.contains("--dry-run");
+                let free = finish(args, command, 1)?;
+
+                AppArguments::Scaffold {
+                    day: required_day(&free, command)?,
+                    download,
+                    overwrite,
+                    template,
+                    dry_run,
+                }
+            }
+            "solve" => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let json = args.contains("--json");
+                let free = finish(args, command, 1)?;
+
+                AppArguments::Solve {
+                    day: required_day(&free, command)?,
+                    release,
+                    dhat,
+                    submit,
+                    json,
+                }
+            }
+            "stars" => {
+                finish(args, command, 0)?;
+                AppArguments::Stars
+            }
+            "status" => {
+                let json = args.contains("--json");
+                finish(args, command, 0)?;
+                AppArguments::Status { json }
+            }
+            "session" => {
+                let free = finish(args, command, 2)?;
+                let action: session::Action = free
+                    .first()
+                    .ok_or_else(|| {
+                        format!(
+                            "missing argument <set|show|check>. Usage: {}",
+                            usage(command)
+                        )
+                    })?
+                    .parse()?;
+
+                if free.len() > 1 && action != session::Action::Set {
+                    return Err(
+                        format!("only `set` accepts a token. Usage: {}", usage(command)).into(),
+                    );
+                }
+
+                AppArguments::Session {
+                    action,
+                    token: free.get(1).cloned(),
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            "doctor" => {
+                finish(args, command, 0)?;
+                AppArguments::Doctor
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            "today" => {
+                let wait = args.contains("--wait");
+                finish(args, command, 0)?;
+                AppArguments::Today { wait }
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            _ => {
+                return Err(format!(
+                    "`{name}` requires a build with the `{name}` feature, use `cargo {name}`."
+                )
+                .into())
             }
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        Ok(app_args)
+    }
+
+    /// Prints the list of commands, or the usage of a single command.
+    pub fn print_help(command: Option<&Command>) {
+        let Some(command) = command else {
+            println!("Usage: cargo <command> [options]");
+            println!();
+            let usages: Vec<String> = COMMANDS
+                .iter()
+                .map(|command| {
+                    format!("{} {}", command.name, command.args)
+                        .trim_end()
+                        .into()
+                })
+                .collect();
+            let width = usages
+                .iter()
+                .map(|usage| usage.chars().count())
+                .chain([COLOR_FLAG.len()])
+                .max()
+                .unwrap_or_default();
+
+            println!("Commands:");
+            for (usage, command) in usages.iter().zip(COMMANDS) {
+                println!("  {usage:<width$} {}", command.about);
+            }
+            println!();
+            println!("Options:");
+            println!("  {COLOR_FLAG:<width$} {COLOR_HELP}");
+            println!();
+            println!("Run `cargo <command> --help` for the options of a command.");
+            return;
+        };
+
+        println!("{}", command.about);
+        println!();
+        println!("Usage: {}", usage(command));
+        println!();
+        let width = command
+            .flags
+            .iter()
+            .map(|(flag, _)| flag.chars().count())
+            .chain([COLOR_FLAG.len(), HELP_FLAG.len()])
+            .max()
+            .unwrap_or_default();
+
+        println!("Options:");
+        for (flag, description) in command.flags {
+            println!("  {flag:<width$} {description}");
         }
+        println!("  {COLOR_FLAG:<width$} {COLOR_HELP}");
+        println!("  {HELP_FLAG:<width$} Print help.");
+    }
 
-        Ok(app_args)
+    const COLOR_FLAG: &str = "--color <when>";
+    const HELP_FLAG: &str = "-h, --help";
+    const COLOR_HELP: &str = "Use colors: `auto` (default), `always` or `never`.";
+
+    fn usage(command: &Command) -> String {
+        let mut usage = format!("cargo {}", command.name);
+        if !command.args.is_empty() {
+            usage.push_str(&format!(" {}", command.args));
+        }
+        if !command.flags.is_empty() {
+            usage.push_str(" [options]");
+        }
+        usage
     }
-}
 
-fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+    fn find_command(name: &str) -> Result<&'static Command, String> {
+        COMMANDS.iter().find(|c| c.name == name).ok_or_else(|| {
+            let names = COMMANDS.iter().map(|c| c.name);
+            format!(
+                "unknown command `{name}`.{} Run with `--help` to list all commands.",
+                suggestion(name, names)
+            )
+        })
+    }
+
+    /// Returns the positional arguments that remain after all flags were parsed.
+    /// Remaining flags are unknown and are reported as errors, as are more than `max` arguments.
+    fn finish(args: Arguments, command: &Command, max: usize) -> Result<Vec<String>, String> {
+        let remaining: Vec<String> = args
+            .finish()
+            .into_iter()
+            .map(|x| x.to_string_lossy().to_string())
+            .collect();
+
+        if let Some(flag) = remaining.iter().find(|x| x.starts_with('-')) {
+            let flags = command
+                .flags
+                .iter()
+                .filter_map(|(flag, _)| flag.split_whitespace().next());
+            return Err(format!(
+                "unknown option `{flag}` for `{}`.{} Run `cargo {} --help` for its options.",
+                command.name,
+                suggestion(flag, flags),
+                command.name
+            ));
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
+
+        if remaining.len() > max {
+            return Err(format!(
+                "unexpected argument `{}`. Usage: {}",
+                remaining[max],
+                usage(command)
+            ));
+        }
+
+        Ok(remaining)
+    }
+
+    fn required_day(free: &[String], command: &Command) -> Result<Day, String> {
+        let day = free
+            .first()
+            .ok_or_else(|| format!("missing argument <day>. Usage: {}", usage(command)))?;
+        parse_day(day)
+    }
+
+    fn parse_day(s: &str) -> Result<Day, String> {
+        s.parse().map_err(|e| format!("invalid day `{s}`: {e}."))
+    }
+
+    fn parse_days(s: &str) -> Result<DaySet, String> {
+        s.parse().map_err(|e| format!("{e}."))
+    }
+
+    /// Suggests the candidate that is closest to a mistyped `input`.
+    fn suggestion<'a>(input: &str, candidates: impl Iterator<Item = &'a str>) -> String {
+        candidates
+            .map(|candidate| (edit_distance(input, candidate), candidate))
+            .filter(|(distance, candidate)| *distance <= candidate.len() / 3 + 1)
+            .min()
+            .map(|(_, candidate)| format!(" Did you mean `{candidate}`?"))
+            .unwrap_or_default()
+    }
+
+    /// Levenshtein distance between two strings.
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+
+        for (i, ca) in a.chars().enumerate() {
+            let mut previous = row[0];
+            row[0] = i + 1;
+
+            for (j, cb) in b.iter().enumerate() {
+                let current = row[j + 1];
+                row[j + 1] = if ca == *cb {
+                    previous
+                } else {
+                    1 + previous.min(row[j]).min(current)
                 };
+                previous = current;
             }
-        },
+        }
+
+        row[b.len()]
+    }
+}
+
+fn main() {
+    let (args, color) = parse().unwrap_or_else(|err| {
+        eprintln!("Error: {err}");
+        process::exit(1);
+    });
+
+    terminal::init(color);
+
+    let result = match args {
+        AppArguments::All {
+            days,
+            release,
+            json,
+        } => all::handle(days, release, json),
+        AppArguments::Time {
+            days,
+            all,
+            store,
+            memory,
+            json,
+        } => time::handle(days, all, store, memory, json),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::DownloadAll { days } => download::handle_all(days),
+        AppArguments::Inputs {
+            action,
+            days,
+            overwrite,
+        } => inputs::handle(action, days, overwrite),
+        AppArguments::Read { day, part } => read::handle(day, part),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+            template,
+            dry_run,
+        } => {
+            // download first, so that the puzzle description can be used to fill in the module template.
+            if download && !dry_run {
+                download::handle(day)
+                    .and_then(|()| scaffold::handle(day, overwrite, template.as_deref(), dry_run))
+            } else {
+                scaffold::handle(day, overwrite, template.as_deref(), dry_run)
+            }
+        }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            json,
+        } => solve::handle(day, release, dhat, submit, json),
+        AppArguments::Stars => stars::handle(),
+        AppArguments::Status { json } => status::handle(json),
+        AppArguments::Session { action, token } => session::handle(action, token),
+        AppArguments::Doctor => doctor::handle(),
+        #[cfg(feature = "today")]
+        AppArguments::Today { wait } => today::handle(wait),
+        AppArguments::Help(command) => {
+            print_help(command);
+            Ok(())
+        }
     };
+
+    if let Err(err) = result {
+        eprintln!("Error: {err}");
+        process::exit(exit_code(&err));
+    }
+}
+
+/// Exit codes of failing solutions are forwarded, so that scripts can tell failures apart.
+fn exit_code(err: &Error) -> i32 {
+    match err {
+        Error::BuildFailed(_) => 2,
+        Error::SolutionFailed(_, Some(code)) => *code,
+        _ => 1,
+    }
 }
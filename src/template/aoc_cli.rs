@@ -1,10 +1,13 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
+    fs,
+    path::PathBuf,
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{config::Config, Day, Part};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -16,42 +19,102 @@ pub enum AocCommandError {
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
+            AocCommandError::CommandNotFound => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
             AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+                write!(
+                    f,
+                    "aoc-cli exited with a non-zero status. Run `cargo session check` if your session cookie expired."
+                )
             }
         }
     }
 }
 
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
+    version().map(|_| ())
+}
+
+/// Returns the version string of the installed aoc-cli, e.g. `aoc-cli 0.12.0`.
+pub fn version() -> Result<String, AocCommandError> {
+    let output = Command::new("aoc")
         .arg("-V")
         .output()
         .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+/// The files aoc-cli reads the session cookie from, in the order they are looked up.
+pub fn session_files() -> Vec<PathBuf> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .or_else(|| env::var_os("APPDATA"))
+        .map(PathBuf::from)
+        .or_else(|| {
+            home.as_ref()
+                .map(|home| PathBuf::from(home).join(".config"))
+        });
+
+    [
+        home.map(|home| PathBuf::from(home).join(".adventofcode.session")),
+        config_dir.map(|dir| dir.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+/// The session cookie aoc-cli authenticates with.
+pub struct Session {
+    pub token: String,
+    /// The file the cookie was read from, [`None`] if it is set in the environment.
+    pub file: Option<PathBuf>,
+}
 
-    call_aoc_cli(&args)
+impl Session {
+    /// Describes where the cookie was read from.
+    pub fn source(&self) -> String {
+        match &self.file {
+            Some(path) => format!("\"{}\"", path.display()),
+            None => format!("the `{SESSION_ENV}` environment variable"),
+        }
+    }
+}
+
+const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+
+/// Reads the session cookie the same way aoc-cli does, or returns [`None`] if no session is configured.
+pub fn read_session() -> Option<Session> {
+    if let Ok(token) = env::var(SESSION_ENV) {
+        if !token.trim().is_empty() {
+            return Some(Session {
+                token: token.trim().into(),
+                file: None,
+            });
+        }
+    }
+
+    session_files().into_iter().find_map(|path| {
+        let token = fs::read_to_string(&path).ok()?;
+        (!token.trim().is_empty()).then(|| Session {
+            token: token.trim().into(),
+            file: Some(path),
+        })
+    })
+}
+
+/// Describes where aoc-cli will load the session cookie from, or returns [`None`] if no session is configured.
+pub fn find_session() -> Option<String> {
+    read_session().map(|session| session.source())
 }
 
 pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+    let config = Config::read_from_file();
+    let input_path = config.paths.input_path(day);
+    let puzzle_path = config.paths.puzzle_path(day);
 
     let args = build_args(
         "download",
@@ -63,6 +126,7 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             puzzle_path.to_string(),
         ],
         day,
+        &config,
     );
 
     let output = call_aoc_cli(&args)?;
@@ -72,33 +136,91 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Downloads only the puzzle description, e.g. to fetch part two after part one was solved.
+pub fn download_puzzle(day: Day) -> Result<Output, AocCommandError> {
+    let config = Config::read_from_file();
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            "--puzzle-only".into(),
+            "--puzzle-file".into(),
+            config.paths.puzzle_path(day),
+        ],
+        day,
+        &config,
+    );
+    call_aoc_cli(&args)
+}
+
+/// Downloads the input and / or the puzzle description of a day, e.g. only the files that are missing.
+/// The output of aoc-cli is captured, it is returned in [`Output::stderr`] on failure.
+pub fn download_files(day: Day, input: bool, puzzle: bool) -> Result<Output, AocCommandError> {
+    let config = Config::read_from_file();
+    let mut args = vec!["--overwrite".to_string()];
+
+    match (input, puzzle) {
+        (true, false) => args.push("--input-only".into()),
+        (false, true) => args.push("--puzzle-only".into()),
+        _ => {}
+    }
+    if input {
+        args.extend(["--input-file".into(), config.paths.input_path(day)]);
+    }
+    if puzzle {
+        args.extend(["--puzzle-file".into(), config.paths.puzzle_path(day)]);
+    }
+
+    let output = Command::new("aoc")
+        .args(build_args("download", &args, day, &config))
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
+
+/// Submits an answer. The response of the server is printed and returned in [`Output::stdout`].
+pub fn submit(day: Day, part: Part, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], day, &Config::read_from_file());
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+/// Checks whether the server response to a submission says that the answer was correct.
+pub fn is_accepted(response: &str) -> bool {
+    response.contains("That's the right answer")
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+/// Checks whether the server response to a submission says that the answer was wrong.
+/// Responses like "You gave an answer too recently" are neither accepted nor rejected.
+pub fn is_rejected(response: &str) -> bool {
+    response.contains("That's not the right answer")
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], day: Day, config: &Config) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = config.year() {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
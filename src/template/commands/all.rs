@@ -1,5 +1,27 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::answers::Answers;
+use crate::template::config::Config;
+use crate::template::report;
+use crate::template::run_multi::run_multi;
+use crate::template::timings::Timings;
+use crate::template::{DaySet, Error};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(days: Option<DaySet>, is_release: bool, is_json: bool) -> Result<(), Error> {
+    let config = Config::read_from_file();
+
+    let days = match days {
+        Some(days) => days.resolve(
+            &config,
+            &Timings::read_from_file(&config.paths.timings),
+            &Answers::read_from_file(&config.paths.answers),
+        ),
+        None => config.event_days().collect(),
+    };
+
+    let run = run_multi(&days, is_release, false, false, is_json)?;
+
+    if is_json {
+        report::print(&run.to_json());
+    }
+
+    run.check()
 }
@@ -0,0 +1,214 @@
+use std::{fs, path::Path};
+
+use crate::template::commands::session;
+use crate::template::config::Config;
+use crate::template::readme_benchmarks::{self, replace_table};
+use crate::template::{aoc_cli, readme_progress, Error};
+
+/// The outcome of a single check.
+struct Check {
+    /// What was checked, phrased as the expected state.
+    description: String,
+    /// How to fix a failed check, [`None`] if it passed.
+    fix: Option<String>,
+}
+
+impl Check {
+    fn pass(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            fix: None,
+        }
+    }
+
+    fn fail(description: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+fn check_config() -> (Config, Vec<Check>) {
+    let (config, check) = match Config::try_read_from_file() {
+        Ok(Some(config)) => (config, Check::pass("\"aoc.toml\" is valid")),
+        Ok(None) => (
+            Config::default(),
+            Check::pass("\"aoc.toml\" is not present, using the defaults"),
+        ),
+        Err(e) => (
+            Config::default(),
+            Check::fail(
+                "\"aoc.toml\" is valid",
+                format!("{e} Fix the file or remove it to use the defaults."),
+            ),
+        ),
+    };
+
+    let year = match config.year() {
+        Some(year) => Check::pass(format!("the event year is {year}")),
+        None => Check::fail(
+            "the event year is set",
+            "Set `AOC_YEAR` in \".cargo/config.toml\" or `year` in \"aoc.toml\".",
+        ),
+    };
+
+    (config, vec![check, year])
+}
+
+fn check_aoc_cli(config: &Config) -> Vec<Check> {
+    let cli = match aoc_cli::version() {
+        Ok(version) => Check::pass(format!("aoc-cli is installed ({version})")),
+        Err(_) => Check::fail(
+            "aoc-cli is installed",
+            "Run `cargo install aoc-cli` to install it.",
+        ),
+    };
+
+    let session = match aoc_cli::read_session() {
+        Some(session) => match session::days_left(&session, config) {
+            Some(days) if days < 0 => Check::fail(
+                format!("the session cookie in {} is not expired", session.source()),
+                "Run `cargo session set` with a new cookie, `cargo session check` tells for sure.",
+            ),
+            _ => Check::pass(format!("a session cookie is set in {}", session.source())),
+        },
+        None => Check::fail(
+            "a session cookie is set",
+            "Run `cargo session set` with the `session` cookie of adventofcode.com.",
+        ),
+    };
+
+    let curl = match session::curl_version() {
+        Some(version) => Check::pass(format!("curl is installed ({version})")),
+        None => Check::fail(
+            "curl is installed",
+            "Install curl, `cargo session` uses it to check the session cookie.",
+        ),
+    };
+
+    vec![cli, session, curl]
+}
+
+fn check_paths(config: &Config) -> Vec<Check> {
+    let paths = &config.paths;
+
+    let mut checks: Vec<Check> = [
+        ("solutions", &paths.bin),
+        ("inputs", &paths.inputs),
+        ("examples", &paths.examples),
+        ("puzzles", &paths.puzzles),
+    ]
+    .into_iter()
+    .map(|(name, dir)| {
+        let description = format!("the {name} directory \"{dir}\" exists");
+        if Path::new(dir).is_dir() {
+            Check::pass(description)
+        } else {
+            Check::fail(description, format!("Run `mkdir -p {dir}` to create it."))
+        }
+    })
+    .collect();
+
+    let template = &paths.template;
+    let description = format!("the module template \"{template}\" exists");
+    checks.push(if Path::new(template).is_file() {
+        Check::pass(description)
+    } else {
+        Check::fail(
+            description,
+            "Restore it from the template repository, or set `paths.template` in \"aoc.toml\".",
+        )
+    });
+
+    checks
+}
+
+fn check_readme(path: &str, readme: Option<&str>) -> Vec<Check> {
+    let Some(readme) = readme else {
+        return vec![Check::fail(
+            format!("the readme \"{path}\" exists"),
+            "Create it, or set `readme.path` in \"aoc.toml\".",
+        )];
+    };
+
+    [
+        ("benchmark", readme_benchmarks::MARKER, "cargo time --store"),
+        ("progress", readme_progress::MARKER, "cargo stars"),
+    ]
+    .into_iter()
+    .map(|(name, marker, command)| {
+        let description = format!("the readme has a {name} table");
+        match replace_table(&mut readme.to_string(), marker, "") {
+            Ok(()) => Check::pass(description),
+            Err(e) => Check::fail(
+                description,
+                format!(
+                    "{e} Add the line `{marker}` twice to \"{path}\", \
+                    `{command}` fills in the table between them."
+                ),
+            ),
+        }
+    })
+    .collect()
+}
+
+pub fn handle() -> Result<(), Error> {
+    let (config, mut checks) = check_config();
+    checks.extend(check_aoc_cli(&config));
+    checks.extend(check_paths(&config));
+
+    let path = &config.readme.path;
+    checks.extend(check_readme(path, fs::read_to_string(path).ok().as_deref()));
+
+    for check in &checks {
+        match &check.fix {
+            None => println!("✓ {}", check.description),
+            Some(fix) => {
+                println!("✖ {}", check.description);
+                println!("  → {fix}");
+            }
+        }
+    }
+
+    let failed = checks.iter().filter(|c| c.fix.is_some()).count();
+    println!();
+
+    if failed > 0 {
+        Err(Error::ChecksFailed(failed))
+    } else {
+        println!("🎄 Everything is set up.");
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::check_readme;
+    use crate::template::{readme_benchmarks, readme_progress};
+
+    #[test]
+    fn checks_readme_markers() {
+        let checks = check_readme("README.md", None);
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].fix.is_some());
+
+        let readme = format!(
+            "# readme\n{0}\n{0}\n{1}",
+            readme_benchmarks::MARKER,
+            readme_progress::MARKER
+        );
+        let checks = check_readme("README.md", Some(&readme));
+        assert!(checks[0].fix.is_none());
+        assert!(checks[1].fix.is_none());
+
+        let readme = format!("{0}{0}{0}", readme_benchmarks::MARKER);
+        let checks = check_readme("README.md", Some(&readme));
+        assert!(checks[0].fix.as_deref().unwrap().contains("Too many"));
+        assert!(checks[1]
+            .fix
+            .as_deref()
+            .unwrap()
+            .contains(readme_progress::MARKER));
+    }
+}
@@ -1,14 +1,249 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{
+    answers::Answers,
+    aoc_cli::{self, AocCommandError},
+    commands::session,
+    config::Config,
+    input::{self, Checksums},
+    puzzle,
+    timings::Timings,
+    Day, DaySet, Error,
+};
+use std::{
+    fs, thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+pub fn handle(day: Day) -> Result<(), Error> {
+    aoc_cli::check()?;
+    session::warn_if_expiring();
+    download(day)?;
+    Ok(())
+}
+
+/// Downloads input and puzzle description of a day and extracts its examples.
+pub fn download(day: Day) -> Result<(), AocCommandError> {
+    aoc_cli::download(day)?;
+    process_download(day, &Config::read_from_file());
+    Ok(())
+}
+
+fn process_download(day: Day, config: &Config) {
+    store_checksum(day, config);
+
+    if config.download.extract_examples {
+        write_examples(day, config);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of downloading a single day with [`handle_all`].
+enum DayDownload {
+    Downloaded,
+    /// Input and puzzle description were already present.
+    Cached,
+    NotReleased,
+    Failed(AocCommandError),
+}
+
+/// Downloads the missing inputs and puzzle descriptions of all released days, or of a selection of days.
+/// Files that are present are skipped, and requests are spaced out by `download.delay_millis`.
+pub fn handle_all(days: Option<DaySet>) -> Result<(), Error> {
+    aoc_cli::check()?;
+    session::warn_if_expiring();
+
+    let config = Config::read_from_file();
+    let mut days: Vec<Day> = match days {
+        Some(days) => days
+            .resolve(
+                &config,
+                &Timings::read_from_file(&config.paths.timings),
+                &Answers::read_from_file(&config.paths.answers),
+            )
+            .into_iter()
+            .collect(),
+        None => config.event_days().collect(),
+    };
+    days.sort_unstable();
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+
+    let mut results = vec![];
+    let mut has_requested = false;
+
+    for day in days {
+        let missing_input = is_missing(&config.paths.input_path(day));
+        let missing_puzzle = is_missing(&config.paths.puzzle_path(day));
+
+        let result = if config
+            .year()
+            .is_some_and(|year| unlock_time(year, day) > now)
+        {
+            DayDownload::NotReleased
+        } else if !missing_input && !missing_puzzle {
+            DayDownload::Cached
+        } else {
+            // don't hammer the servers of adventofcode.com.
+            if has_requested {
+                thread::sleep(Duration::from_millis(config.download.delay_millis));
+            }
+            has_requested = true;
+
+            println!("Downloading day {day}...");
+            match aoc_cli::download_files(day, missing_input, missing_puzzle) {
+                Ok(_) => {
+                    process_download(day, &config);
+                    DayDownload::Downloaded
+                }
+                Err(e) => DayDownload::Failed(e),
+            }
+        };
+
+        results.push((day, result));
+    }
+
+    println!("---");
+    for (day, result) in &results {
+        match result {
+            DayDownload::Downloaded => println!("Day {day}: ✓ downloaded"),
+            DayDownload::Cached => println!("Day {day}: ✓ cached"),
+            DayDownload::NotReleased => println!("Day {day}: - not released yet"),
+            DayDownload::Failed(e) => {
+                println!("Day {day}: ✖ {e}");
+                if let AocCommandError::BadExitStatus(output) = e {
+                    for line in String::from_utf8_lossy(&output.stderr).lines() {
+                        println!("    {line}");
+                    }
+                }
+            }
+        }
     }
 
-    if let Err(e) = aoc_cli::download(day) {
+    let count = |f: fn(&DayDownload) -> bool| results.iter().filter(|(_, x)| f(x)).count();
+    println!(
+        "🎄 Downloaded: {}, cached: {}, not released: {}.",
+        count(|x| matches!(x, DayDownload::Downloaded)),
+        count(|x| matches!(x, DayDownload::Cached)),
+        count(|x| matches!(x, DayDownload::NotReleased)),
+    );
+
+    let failed: Vec<Day> = results
+        .iter()
+        .filter(|(_, x)| matches!(x, DayDownload::Failed(_)))
+        .map(|(day, _)| *day)
+        .collect();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::DownloadsFailed(failed))
+    }
+}
+
+/// Missing or empty files, e.g. an input file created by `scaffold`, are downloaded.
+fn is_missing(path: &str) -> bool {
+    fs::read_to_string(path).map_or(true, |x| x.trim().is_empty())
+}
+
+/// The unix timestamp at which the puzzle of a day unlocks, i.e. at midnight EST (UTC-5).
+fn unlock_time(year: u16, day: Day) -> u64 {
+    // days since the unix epoch of December `day`, see http://howardhinnant.github.io/date_algorithms.html
+    let (year, month, day) = (u64::from(year), 12, u64::from(day.into_inner()));
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let day_of_year = (153 * (month - 3) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    days * 86_400 + 5 * 3_600
+}
+
+/// Records the checksum of a freshly downloaded input, so that later edits can be detected by `read_file`.
+fn store_checksum(day: Day, config: &Config) {
+    let Ok(text) = fs::read_to_string(config.paths.input_path(day)) else {
+        return;
+    };
+
+    if let Some(problem) = input::check(&text) {
+        eprintln!("Warning: the downloaded input {problem}.");
+        return;
+    }
+
+    let mut checksums = Checksums::read_from_file(&config.paths.checksums);
+    checksums.data.insert(day, input::checksum(&text));
+    if let Err(e) = checksums.store_file(&config.paths.checksums) {
+        eprintln!("Failed to store input checksum: {e}");
+    }
+}
+
+/// Downloads the puzzle description again and prints the text that was not present before,
+/// i.e. the description of part two after part one was accepted.
+pub fn refresh_puzzle(day: Day, config: &Config) {
+    let path = config.paths.puzzle_path(day);
+    let previous = fs::read_to_string(&path).unwrap_or_default();
+
+    println!("Downloading the updated puzzle description...");
+    if let Err(e) = aoc_cli::download_puzzle(day) {
         eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+        return;
+    }
+
+    let current = fs::read_to_string(&path).unwrap_or_default();
+
+    match puzzle::unlocked_text(&previous, &current) {
+        Some(text) => {
+            println!("---");
+            println!("{}", text.trim());
+            println!("---");
+        }
+        None => println!("No new puzzle text was unlocked."),
+    }
+
+    if config.download.extract_examples {
+        write_examples(day, config);
+    }
+}
+
+/// Writes the example candidates found in the puzzle description to the examples directory.
+/// The first candidate is written to `DD.txt`, further candidates to `DD.example-2.txt`, `DD.example-3.txt` etc.
+/// They do not collide with the `DD-{part}.txt` files of `read_file_part`, which are only created by hand.
+/// Files that already have contents are left untouched.
+pub fn write_examples(day: Day, config: &Config) {
+    let Ok(markdown) = fs::read_to_string(config.paths.puzzle_path(day)) else {
+        return;
     };
+
+    for (i, example) in puzzle::example_blocks(&markdown).iter().enumerate() {
+        let path = if i == 0 {
+            config.paths.example_path(day)
+        } else {
+            format!("{}/{day}.example-{}.txt", config.paths.examples, i + 1)
+        };
+
+        let is_empty = fs::read_to_string(&path).map_or(true, |x| x.trim().is_empty());
+        if !is_empty {
+            continue;
+        }
+
+        match fs::write(&path, example) {
+            Ok(()) => println!("🎄 Extracted example to \"{path}\"."),
+            Err(e) => eprintln!("Failed to write example file \"{path}\": {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::unlock_time;
+    use crate::day;
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(unlock_time(2024, day!(1)), 1_733_029_200);
+        // 2015-12-25T05:00:00Z
+        assert_eq!(unlock_time(2015, day!(25)), 1_451_019_600);
+    }
 }
@@ -0,0 +1,158 @@
+use std::{collections::HashSet, fmt::Display, fs, str::FromStr};
+
+use crate::template::answers::Answers;
+use crate::template::config::Config;
+use crate::template::crypto::{self, Key};
+use crate::template::timings::Timings;
+use crate::template::{Day, DaySet, Error};
+
+/// What `cargo inputs` does with the selected inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Encrypt,
+    Decrypt,
+}
+
+impl FromStr for Action {
+    type Err = ActionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "encrypt" => Ok(Self::Encrypt),
+            "decrypt" => Ok(Self::Decrypt),
+            _ => Err(ActionFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`Action`].
+#[derive(Debug)]
+pub struct ActionFromStrError;
+
+impl std::error::Error for ActionFromStrError {}
+
+impl Display for ActionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting `encrypt` or `decrypt`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn handle(action: Action, days: Option<DaySet>, overwrite: bool) -> Result<(), Error> {
+    let config = Config::read_from_file();
+
+    let days: HashSet<Day> = match days {
+        Some(days) => days.resolve(
+            &config,
+            &Timings::read_from_file(&config.paths.timings),
+            &Answers::read_from_file(&config.paths.answers),
+        ),
+        None => config.event_days().collect(),
+    };
+    let mut days: Vec<Day> = days.into_iter().collect();
+    days.sort_unstable();
+
+    match action {
+        Action::Encrypt => encrypt(&days, &config),
+        Action::Decrypt => decrypt(&days, &config, overwrite),
+    }
+}
+
+fn encrypt(days: &[Day], config: &Config) -> Result<(), Error> {
+    let key = match crypto::read_key(config) {
+        Some(key) => key.map_err(Error::Encryption)?,
+        None => {
+            let key = crypto::generate_key(config).map_err(Error::Encryption)?;
+            println!(
+                "🔑 Generated a new key in \"{}\". Keep a copy of it, the inputs can't be decrypted without it.",
+                config.paths.input_key
+            );
+            key
+        }
+    };
+
+    fs::create_dir_all(&config.paths.encrypted)
+        .map_err(|e| Error::File(config.paths.encrypted.clone(), e))?;
+
+    let mut encrypted = 0;
+    for day in days {
+        let Ok(text) = fs::read_to_string(config.paths.input_path(*day)) else {
+            continue;
+        };
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        // every encryption uses a new nonce, so unchanged inputs are detected by decrypting the existing copy.
+        let path = config.paths.encrypted_input_path(*day);
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        if crypto::decrypt(&existing, &key).is_ok_and(|x| x == text) {
+            continue;
+        }
+
+        let contents = crypto::encrypt(&text, &key).map_err(Error::Encryption)?;
+        fs::write(&path, contents).map_err(|e| Error::File(path.clone(), e))?;
+        println!("🔒 Encrypted input of day {day} to \"{path}\".");
+        encrypted += 1;
+    }
+
+    println!("Encrypted {encrypted} changed inputs.");
+    Ok(())
+}
+
+fn decrypt(days: &[Day], config: &Config, overwrite: bool) -> Result<(), Error> {
+    let key = read_key(config)?;
+
+    let mut decrypted = 0;
+    for day in days {
+        let Ok(encrypted) = fs::read_to_string(config.paths.encrypted_input_path(*day)) else {
+            continue;
+        };
+
+        let text = crypto::decrypt(&encrypted, &key).map_err(|e| {
+            Error::Encryption(format!(
+                "could not decrypt \"{}\": {e}",
+                config.paths.encrypted_input_path(*day)
+            ))
+        })?;
+
+        let path = config.paths.input_path(*day);
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        if !existing.trim().is_empty() && existing != text && !overwrite {
+            eprintln!(
+                "Skipped \"{path}\", it differs from its encrypted copy. Use `--overwrite` to replace it."
+            );
+            continue;
+        }
+
+        if write_if_changed(&path, &text)? {
+            println!("🔓 Decrypted input of day {day} to \"{path}\".");
+            decrypted += 1;
+        }
+    }
+
+    println!("Decrypted {decrypted} inputs.");
+    Ok(())
+}
+
+fn read_key(config: &Config) -> Result<Key, Error> {
+    crypto::read_key(config)
+        .ok_or_else(|| {
+            Error::Encryption(format!(
+                "no key found. Set `AOC_INPUT_KEY` or copy the key to \"{}\".",
+                config.paths.input_key
+            ))
+        })?
+        .map_err(Error::Encryption)
+}
+
+/// Returns whether the file was written, i.e. its contents changed.
+fn write_if_changed(path: &str, contents: &str) -> Result<bool, Error> {
+    if fs::read_to_string(path).is_ok_and(|x| x == contents) {
+        return Ok(false);
+    }
+
+    fs::write(path, contents).map_err(|e| Error::File(path.into(), e))?;
+    Ok(true)
+}
@@ -1,6 +1,13 @@
 pub mod all;
+pub mod doctor;
 pub mod download;
+pub mod inputs;
 pub mod read;
 pub mod scaffold;
+pub mod session;
 pub mod solve;
+pub mod stars;
+pub mod status;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
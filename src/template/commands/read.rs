@@ -1,15 +1,104 @@
-use std::process;
+use std::{
+    env, fs,
+    io::Write,
+    process::{Command, Stdio},
+};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{config::Config, puzzle, render, terminal, Day, Error, Part};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+const DEFAULT_WIDTH: usize = 80;
+
+/// Prints the stored puzzle description, optionally only the description of one `part`.
+pub fn handle(day: Day, part: Option<Part>) -> Result<(), Error> {
+    let path = Config::read_from_file().paths.puzzle_path(day);
+
+    let Ok(markdown) = fs::read_to_string(&path) else {
+        return Err(Error::PuzzleNotFound(day, path));
+    };
+
+    let sections = puzzle::sections(&markdown);
+
+    let text = match part {
+        None => markdown.as_str(),
+        Some(Part::One) => sections[0],
+        Some(Part::Two) => sections.get(1).ok_or(Error::PartNotUnlocked(day))?,
+    };
+
+    let is_terminal = terminal::is_interactive();
+
+    let width = if is_terminal {
+        terminal_width().unwrap_or(DEFAULT_WIDTH)
+    } else {
+        DEFAULT_WIDTH
+    };
+
+    let rendered = terminal::styled(render::render(text, width));
+
+    if !is_terminal || !page(&rendered) {
+        println!("{rendered}");
+    }
+
+    Ok(())
+}
+
+/// Reads the terminal width from `$COLUMNS`, falling back to `stty`.
+fn terminal_width() -> Option<usize> {
+    if let Some(columns) = env::var("COLUMNS").ok().and_then(|x| x.parse().ok()) {
+        return Some(columns);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let output = Command::new("stty")
+        .arg("size")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    // `stty size` prints "<rows> <columns>".
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+/// Shows `text` in `$PAGER` (or `less`). Returns `false` if no pager could be started.
+fn page(text: &str) -> bool {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less".into());
+    let mut parts = pager.split_whitespace();
+
+    let Some(program) = parts.next() else {
+        return false;
+    };
+
+    let mut command = Command::new(program);
+    command.args(parts).stdin(Stdio::piped());
+
+    // keep colors, and skip the pager for descriptions that fit on a single screen.
+    if program == "less" {
+        command.env("LESS", "FRX");
+    }
+
+    let Ok(mut child) = command.spawn() else {
+        return false;
     };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // the user may quit the pager before reading everything.
+        let _ = writeln!(stdin, "{text}");
+    }
+
+    child.wait().is_ok()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::handle;
+    use crate::{day, template::Error};
+
+    #[test]
+    fn errors_if_puzzle_not_downloaded() {
+        let result = handle(day!(25), None);
+        assert!(matches!(result, Err(Error::PuzzleNotFound(day, _)) if day == day!(25)));
+    }
 }
@@ -1,79 +1,379 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
 };
 
-use crate::template::Day;
+use crate::template::{config::Config, puzzle, Day, Error};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+/// Reads the module template, either the named template or the default one.
+/// Falls back to the template that is compiled into the binary if the default template file is not present.
+fn read_module_template(config: &Config, name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        return Ok(
+            fs::read_to_string(&config.paths.template).unwrap_or_else(|_| MODULE_TEMPLATE.into())
+        );
+    };
+
+    let path = config.templates.get(name).ok_or_else(|| {
+        let available: Vec<&str> = config.templates.keys().map(String::as_str).collect();
+        format!(
+            "unknown template `{name}`. Available templates: {}.",
+            if available.is_empty() {
+                "none, add them to the `[templates]` section of aoc.toml".into()
+            } else {
+                available.join(", ")
+            }
+        )
+    })?;
+
+    fs::read_to_string(path).map_err(|e| format!("could not read template \"{path}\": {e}"))
+}
+
+/// Values that are substituted into the module template.
+struct Placeholders {
+    day: Day,
+    year: Option<u16>,
+    title: Option<String>,
+    example_answers: [Option<String>; 2],
+}
+
+impl Placeholders {
+    /// `markdown` is the puzzle description, or an empty string if it was not downloaded.
+    fn new(day: Day, config: &Config, markdown: &str) -> Self {
+        Self {
+            day,
+            year: config.year(),
+            title: puzzle::title(markdown),
+            example_answers: puzzle::example_answers(markdown),
+        }
+    }
+
+    fn render(&self, template: &str) -> String {
+        // the template returns `Option<u64>`, other answers are kept as a comment so that the module still compiles.
+        let answer = |x: &Option<String>| match x {
+            Some(x) if x.parse::<u64>().is_ok() => format!("Some({x})"),
+            Some(x) => format!("None /* example answer: {:?} */", x.replace("*/", "* /")),
+            None => "None".into(),
+        };
+
+        template
+            .replace("%DAY_NUMBER%", &self.day.into_inner().to_string())
+            .replace("%DAY_PADDED%", &self.day.to_string())
+            .replace(
+                "%YEAR%",
+                &self.year.map(|x| x.to_string()).unwrap_or_default(),
+            )
+            .replace("%PUZZLE_TITLE%", self.title.as_deref().unwrap_or_default())
+            .replace("%EXAMPLE_ANSWER_1%", &answer(&self.example_answers[0]))
+            .replace("%EXAMPLE_ANSWER_2%", &answer(&self.example_answers[1]))
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// What scaffold does with a single file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    Create,
+    /// The file exists and is left untouched.
+    Skip,
+    /// The file exists and is replaced after moving it to a backup location.
+    Replace,
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+struct ScaffoldFile {
+    kind: &'static str,
+    path: String,
+    contents: String,
+    action: Action,
+    /// Where a replaced file is moved to, existing backups are never overwritten.
+    backup_path: String,
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+impl ScaffoldFile {
+    /// Existing files are only replaced if `replace_existing` is set, they are skipped otherwise.
+    fn plan(kind: &'static str, path: String, contents: String, replace_existing: bool) -> Self {
+        let action = match (Path::new(&path).exists(), replace_existing) {
+            (false, _) => Action::Create,
+            (true, false) => Action::Skip,
+            (true, true) => Action::Replace,
+        };
+
+        let backup_path = free_backup_path(&path);
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+        Self {
+            kind,
+            path,
+            contents,
+            action,
+            backup_path,
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    }
+
+    fn describe(&self, dry_run: bool) -> String {
+        let empty = if self.contents.is_empty() {
+            "empty "
+        } else {
+            ""
+        };
+        let (kind, path) = (self.kind, &self.path);
+
+        match (self.action, dry_run) {
+            (Action::Create, false) => format!("Created {empty}{kind} file \"{path}\""),
+            (Action::Create, true) => format!("Would create {empty}{kind} file \"{path}\""),
+            (Action::Skip, false) => format!("Skipped existing {kind} file \"{path}\""),
+            (Action::Skip, true) => format!("Would skip existing {kind} file \"{path}\""),
+            (Action::Replace, false) => format!(
+                "Replaced {kind} file \"{path}\", backup at \"{}\"",
+                self.backup_path
+            ),
+            (Action::Replace, true) => format!(
+                "Would replace {kind} file \"{path}\", backup at \"{}\"",
+                self.backup_path
+            ),
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    fn write(&self) -> Result<(), io::Error> {
+        if self.action == Action::Replace {
+            // `rename` replaces existing files, a backup created since planning must not be lost.
+            if Path::new(&self.backup_path).exists() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("backup \"{}\" already exists", self.backup_path),
+                ));
+            }
+            fs::rename(&self.path, &self.backup_path)?;
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+
+        let mut created = false;
+        let result = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&self.path)
+            .and_then(|mut file| {
+                created = true;
+                file.write_all(self.contents.as_bytes())
+            });
+
+        if result.is_err() {
+            self.undo(created);
         }
+
+        result
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    fn undo(&self, created: bool) {
+        if created {
+            let _ = fs::remove_file(&self.path);
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+
+        if self.action == Action::Replace {
+            let _ = fs::rename(&self.backup_path, &self.path);
         }
     }
+}
+
+/// Returns `{path}.bak`, or `{path}.bak.{n}` with the lowest `n` that is not taken by an earlier backup.
+fn free_backup_path(path: &str) -> String {
+    let mut backup_path = format!("{path}.bak");
+    let mut n = 1;
+
+    while Path::new(&backup_path).exists() {
+        backup_path = format!("{path}.bak.{n}");
+        n += 1;
+    }
+
+    backup_path
+}
+
+/// Writes all files that are not skipped. If one of them fails,
+/// the files written before are removed again and replaced files are restored from their backup.
+fn apply(files: &[ScaffoldFile]) -> Result<(), String> {
+    let mut written: Vec<&ScaffoldFile> = vec![];
+
+    for file in files.iter().filter(|f| f.action != Action::Skip) {
+        if let Err(e) = file.write() {
+            for file in written.iter().rev() {
+                file.undo(true);
+            }
+
+            return Err(format!(
+                "Failed to create {} file \"{}\": {e}",
+                file.kind, file.path
+            ));
+        }
+
+        written.push(file);
+    }
+
+    Ok(())
+}
+
+pub fn handle(
+    day: Day,
+    overwrite: bool,
+    template: Option<&str>,
+    dry_run: bool,
+) -> Result<(), Error> {
+    let config = Config::read_from_file();
+
+    let module_template = read_module_template(&config, template)
+        .map_err(|e| Error::Template(format!("failed to read module template: {e}")))?;
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    let markdown = fs::read_to_string(config.paths.puzzle_path(day)).unwrap_or_default();
+    let module = Placeholders::new(day, &config, &markdown).render(&module_template);
+    let example = puzzle::example_blocks(&markdown)
+        .into_iter()
+        .next()
+        .unwrap_or_default();
+
+    // puzzle inputs and examples are never replaced, they might hold downloaded or hand-pasted data.
+    let files = [
+        ScaffoldFile::plan("module", config.paths.bin_path(day), module, overwrite),
+        ScaffoldFile::plan("input", config.paths.input_path(day), String::new(), false),
+        ScaffoldFile::plan("example", config.paths.example_path(day), example, false),
+    ];
+
+    if !dry_run {
+        apply(&files).map_err(Error::Scaffold)?;
+    }
+
+    for file in &files {
+        println!("{}", file.describe(dry_run));
+    }
+
+    if files[0].action == Action::Skip {
+        println!("Use `--overwrite` to replace the existing module file.");
+    }
+
+    if !dry_run {
+        println!("---");
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{apply, Action, Placeholders, ScaffoldFile};
+    use crate::day;
+    use std::{env, fs};
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(3),
+            year: Some(2024),
+            title: Some("Mull It Over".into()),
+            example_answers: [Some("161".into()), Some("a,b".into())],
+        };
+
+        let template = "// %YEAR% day %DAY_NUMBER% (%DAY_PADDED%): %PUZZLE_TITLE%\n%EXAMPLE_ANSWER_1% %EXAMPLE_ANSWER_2%";
+        assert_eq!(
+            placeholders.render(template),
+            "// 2024 day 3 (03): Mull It Over\nSome(161) None /* example answer: \"a,b\" */"
+        );
+    }
+
+    #[test]
+    fn renders_answers_as_u64() {
+        let placeholders = Placeholders {
+            day: day!(3),
+            year: None,
+            title: None,
+            example_answers: [Some("18446744073709551615".into()), Some("-3".into())],
+        };
+
+        assert_eq!(
+            placeholders.render("%EXAMPLE_ANSWER_1% %EXAMPLE_ANSWER_2%"),
+            "Some(18446744073709551615) None /* example answer: \"-3\" */"
+        );
+    }
+
+    #[test]
+    fn renders_missing_values() {
+        let placeholders = Placeholders {
+            day: day!(12),
+            year: None,
+            title: None,
+            example_answers: [None, None],
+        };
+
+        assert_eq!(
+            placeholders.render("%YEAR%|%PUZZLE_TITLE%|%EXAMPLE_ANSWER_1%"),
+            "||None"
+        );
+    }
+
+    #[test]
+    fn keeps_existing_files_and_rolls_back_on_failure() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let existing = dir.join("existing.txt").to_string_lossy().to_string();
+        let replaced = dir.join("replaced.rs").to_string_lossy().to_string();
+        let created = dir.join("created.txt").to_string_lossy().to_string();
+        let broken = dir.join("missing/broken.txt").to_string_lossy().to_string();
+
+        fs::write(&existing, "input").unwrap();
+        fs::write(&replaced, "old").unwrap();
+
+        let files = [
+            ScaffoldFile::plan("input", existing.clone(), String::new(), false),
+            ScaffoldFile::plan("module", replaced.clone(), "new".into(), true),
+            ScaffoldFile::plan("example", created.clone(), String::new(), false),
+            ScaffoldFile::plan("example", broken, String::new(), false),
+        ];
+
+        assert_eq!(files[0].action, Action::Skip);
+        assert_eq!(files[1].action, Action::Replace);
+        assert_eq!(files[2].action, Action::Create);
+
+        assert!(apply(&files).is_err());
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "input");
+        assert_eq!(fs::read_to_string(&replaced).unwrap(), "old");
+        assert!(!fs::exists(&created).unwrap());
+        assert!(!fs::exists(format!("{replaced}.bak")).unwrap());
+
+        assert!(apply(&files[..3]).is_ok());
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "input");
+        assert_eq!(fs::read_to_string(&replaced).unwrap(), "new");
+        assert_eq!(
+            fs::read_to_string(format!("{replaced}.bak")).unwrap(),
+            "old"
+        );
+        assert!(fs::exists(&created).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_earlier_backups() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-backup-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let module = dir.join("01.rs").to_string_lossy().to_string();
+        fs::write(&module, "original").unwrap();
+
+        for contents in ["first", "second"] {
+            let file = ScaffoldFile::plan("module", module.clone(), contents.into(), true);
+            assert!(apply(&[file]).is_ok());
+        }
+
+        assert_eq!(fs::read_to_string(&module).unwrap(), "second");
+        assert_eq!(
+            fs::read_to_string(format!("{module}.bak")).unwrap(),
+            "original"
+        );
+        assert_eq!(
+            fs::read_to_string(format!("{module}.bak.1")).unwrap(),
+            "first"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
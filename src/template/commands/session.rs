@@ -0,0 +1,259 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    process::{Command, Stdio},
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::aoc_cli::{self, Session};
+use crate::template::config::Config;
+use crate::template::{write_secret, Error};
+
+/// Expiring sessions are reported this many days in advance.
+const WARN_DAYS: i64 = 7;
+
+/// What `cargo session` does with the session cookie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Set,
+    Show,
+    Check,
+}
+
+impl FromStr for Action {
+    type Err = ActionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "set" => Ok(Self::Set),
+            "show" => Ok(Self::Show),
+            "check" => Ok(Self::Check),
+            _ => Err(ActionFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`Action`].
+#[derive(Debug)]
+pub struct ActionFromStrError;
+
+impl std::error::Error for ActionFromStrError {}
+
+impl Display for ActionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting `set`, `show` or `check`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn handle(action: Action, token: Option<String>) -> Result<(), Error> {
+    let config = Config::read_from_file();
+
+    match action {
+        Action::Set => set(token, &config),
+        Action::Show => show(&config),
+        Action::Check => check(&config),
+    }
+}
+
+fn set(token: Option<String>, config: &Config) -> Result<(), Error> {
+    // reading the cookie from stdin keeps it out of the shell history.
+    let token = match token {
+        Some(token) => token,
+        None => {
+            print!("Paste the `session` cookie of adventofcode.com: ");
+            let _ = io::stdout().flush();
+            let mut line = String::new();
+            io::stdin().read_line(&mut line)?;
+            line
+        }
+    };
+
+    let token = parse_token(&token).ok_or_else(|| {
+        Error::Session(
+            "expected a hex string, copy the value of the `session` cookie of adventofcode.com."
+                .into(),
+        )
+    })?;
+
+    let path = aoc_cli::session_files()
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::Session("could not determine the home directory.".into()))?;
+
+    write_secret(&path, &format!("{token}\n"))
+        .map_err(|e| Error::File(path.display().to_string(), e))?;
+    println!("🔑 Stored the session cookie in \"{}\".", path.display());
+
+    if let Some(session) = aoc_cli::read_session() {
+        if session.file.as_ref() != Some(&path) {
+            eprintln!(
+                "Warning: aoc-cli uses the session cookie from {} instead.",
+                session.source()
+            );
+        }
+    }
+
+    match validate(&config.session.server, &token) {
+        Ok(true) => println!("✓ The session cookie is valid."),
+        Ok(false) => eprintln!("Warning: the session cookie was rejected."),
+        Err(e) => eprintln!("Warning: could not check the session cookie: {e}"),
+    }
+
+    Ok(())
+}
+
+fn show(config: &Config) -> Result<(), Error> {
+    let session = read_session()?;
+
+    println!("Session: {}", mask(&session.token));
+    println!("Source:  {}", session.source());
+
+    match days_left(&session, config) {
+        Some(days) if days < 0 => println!("Expiry:  probably expired {} days ago", -days),
+        Some(days) => println!("Expiry:  in about {days} days"),
+        None => println!("Expiry:  unknown"),
+    }
+
+    Ok(())
+}
+
+fn check(config: &Config) -> Result<(), Error> {
+    let session = read_session()?;
+    let server = &config.session.server;
+
+    match validate(server, &session.token) {
+        Ok(true) => {
+            println!("✓ The session cookie from {} is valid.", session.source());
+            warn_if_expiring();
+            Ok(())
+        }
+        Ok(false) => Err(Error::Session(format!(
+            "the session cookie from {} was rejected by {server}, it probably expired. \
+            Run `cargo session set` with a new cookie.",
+            session.source()
+        ))),
+        Err(e) => Err(Error::Session(e)),
+    }
+}
+
+/// Prints a warning if the session cookie expires soon, e.g. before downloading.
+pub fn warn_if_expiring() {
+    let Some(session) = aoc_cli::read_session() else {
+        return;
+    };
+
+    match days_left(&session, &Config::read_from_file()) {
+        Some(days) if days < 0 => eprintln!(
+            "Warning: the session cookie is probably expired. Run `cargo session set` with a new cookie."
+        ),
+        Some(days) if days <= WARN_DAYS => eprintln!(
+            "Warning: the session cookie expires in about {days} days. Run `cargo session set` with a new cookie."
+        ),
+        _ => {}
+    }
+}
+
+/// Estimates the days until the session cookie expires, based on when its file was written.
+/// Returns [`None`] for cookies set in the environment.
+pub fn days_left(session: &Session, config: &Config) -> Option<i64> {
+    let modified = fs::metadata(session.file.as_ref()?).ok()?.modified().ok()?;
+    let expires = modified + Duration::from_secs(config.session.lifetime_days * 86_400);
+
+    #[allow(clippy::cast_possible_wrap)]
+    let days = match expires.duration_since(SystemTime::now()) {
+        Ok(left) => (left.as_secs() / 86_400) as i64,
+        Err(e) => -((e.duration().as_secs() / 86_400) as i64) - 1,
+    };
+    Some(days)
+}
+
+fn read_session() -> Result<Session, Error> {
+    aoc_cli::read_session().ok_or_else(|| {
+        Error::Session("no session cookie is set. Run `cargo session set` to store one.".into())
+    })
+}
+
+/// Accepts the cookie value, also when it was copied with its name, i.e. `session=...`.
+fn parse_token(s: &str) -> Option<String> {
+    let token = s.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+    (!token.is_empty() && token.chars().all(|c| c.is_ascii_hexdigit())).then(|| token.into())
+}
+
+fn mask(token: &str) -> String {
+    if token.len() <= 12 {
+        return "*".repeat(token.len());
+    }
+    format!("{}…{}", &token[..6], &token[token.len() - 4..])
+}
+
+/// Returns the version of the installed curl, e.g. `curl 8.5.0`, which is used to check the session cookie.
+pub fn curl_version() -> Option<String> {
+    let output = Command::new("curl").arg("--version").output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = stdout
+        .split_whitespace()
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!version.is_empty()).then_some(version)
+}
+
+/// Requests the settings page of `server`, which is only accessible with a valid session.
+/// Returns `Ok(false)` if the server rejects the cookie.
+fn validate(server: &str, token: &str) -> Result<bool, String> {
+    let mut child = Command::new("curl")
+        .args([
+            "--silent",
+            "--output",
+            if cfg!(windows) { "NUL" } else { "/dev/null" },
+        ])
+        .args(["--write-out", "%{http_code}", "--max-time", "10"])
+        // the cookie is passed as config on stdin, so that it does not show up in the process list.
+        .args(["--config", "-"])
+        .arg(format!("{}/settings", server.trim_end_matches('/')))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|_| "`curl` is required to check the session cookie.".to_string())?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "cookie = \"session={token}\"").map_err(|e| e.to_string())?;
+    }
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    let code = String::from_utf8_lossy(&output.stdout);
+
+    match code.trim() {
+        "200" => Ok(true),
+        "302" | "400" | "401" | "403" => Ok(false),
+        "000" => Err(format!("could not connect to {server}.")),
+        code => Err(format!("unexpected response `{code}` from {server}.")),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{mask, parse_token};
+
+    #[test]
+    fn parses_tokens() {
+        assert_eq!(
+            parse_token(" 53616c7465640a\n"),
+            Some("53616c7465640a".into())
+        );
+        assert_eq!(
+            parse_token("session=53616c7465640a"),
+            Some("53616c7465640a".into())
+        );
+        assert_eq!(parse_token(""), None);
+        assert_eq!(parse_token("not a token"), None);
+
+        assert_eq!(mask("53616c7465640a1234"), "53616c…1234");
+        assert_eq!(mask("abc"), "***");
+    }
+}
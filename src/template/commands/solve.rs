@@ -1,21 +1,45 @@
-use std::process::{Command, Stdio};
+use std::process::{Command, ExitStatus, Stdio};
 
-use crate::template::Day;
+use crate::template::report::{self, Outcome};
+use crate::template::{Day, Error, Part};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<Part>,
+    json: bool,
+) -> Result<(), Error> {
+    let mut build_args = vec!["--bin".to_string(), day.to_string()];
 
     if dhat {
-        cmd_args.extend([
+        build_args.extend([
             "--profile".to_string(),
             "dhat".to_string(),
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
     } else if release {
-        cmd_args.push("--release".to_string());
+        build_args.push("--release".to_string());
     }
 
+    // build separately, so that build failures can be told apart from failing solutions.
+    let status = Command::new("cargo")
+        .arg("build")
+        .args(&build_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()?;
+
+    if !status.success() {
+        if json {
+            report::print(&report::day_report(day, &Outcome::BuildFailed, &[]));
+        }
+        return Err(Error::BuildFailed(day));
+    }
+
+    let mut cmd_args = vec!["run".to_string()];
+    cmd_args.extend(build_args);
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,12 +47,54 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
+    let status = if json {
+        cmd_args.push("--json".to_string());
+        run_json(day, &cmd_args)?
+    } else {
+        Command::new("cargo")
+            .args(&cmd_args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?
+    };
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::SolutionFailed(day, status.code()))
+    }
+}
+
+/// Runs the solution with `--json` and prints its report. Output that is not part of the report is forwarded to stderr.
+fn run_json(day: Day, cmd_args: &[String]) -> Result<ExitStatus, Error> {
+    let output = Command::new("cargo")
+        .args(cmd_args)
+        .stdout(Stdio::piped())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .output()?;
+
+    let lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(String::from)
+        .collect();
+
+    for line in &lines {
+        if report::parse_record(line).is_none() {
+            eprintln!("{line}");
+        }
+    }
+
+    let outcome = if output.status.success() {
+        Outcome::Solved
+    } else {
+        Outcome::Failed(output.status.code())
+    };
+
+    report::print(&report::day_report(
+        day,
+        &outcome,
+        &report::parse_records(&lines),
+    ));
 
-    cmd.wait().unwrap();
+    Ok(output.status)
 }
@@ -0,0 +1,22 @@
+use crate::template::answers::Answers;
+use crate::template::config::Config;
+use crate::template::readme_progress::{self, DayProgress};
+use crate::template::timings::Timings;
+use crate::template::Error;
+
+pub fn handle() -> Result<(), Error> {
+    let config = Config::read_from_file();
+    let progress = readme_progress::collect(
+        &Timings::read_from_file(&config.paths.timings),
+        &Answers::read_from_file(&config.paths.answers),
+        &config,
+    );
+    let total_stars: usize = progress.iter().map(DayProgress::stars).sum();
+
+    println!("⭐ Collected {total_stars} stars.");
+
+    readme_progress::update(&progress, &config)?;
+    println!("Stored updated progress.");
+
+    Ok(())
+}
@@ -0,0 +1,325 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::config::Config;
+use crate::template::report::{self, number};
+use crate::template::terminal;
+use crate::template::timings::Timings;
+use crate::template::{Day, Error, ANSI_BOLD, ANSI_RESET};
+
+/// The state of a data file of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileState {
+    Missing,
+    /// The file exists but only contains whitespace, e.g. an input file created by `scaffold`.
+    Empty,
+    Present,
+}
+
+impl FileState {
+    fn of(path: &str) -> Self {
+        match fs::read(path) {
+            Ok(bytes) if bytes.iter().all(u8::is_ascii_whitespace) => Self::Empty,
+            Ok(_) => Self::Present,
+            Err(_) => Self::Missing,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Missing => "✗",
+            Self::Empty => "empty",
+            Self::Present => "✓",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::Empty => "empty",
+            Self::Present => "present",
+        }
+    }
+}
+
+/// Represents the state of a single day's files.
+#[derive(Clone, Debug)]
+struct DayStatus {
+    day: Day,
+    parts: u8,
+    has_bin: bool,
+    input: FileState,
+    example: FileState,
+    puzzle: FileState,
+    /// The number of parts with a stored benchmark.
+    timings: u8,
+    /// The number of parts with a known answer.
+    answers: u8,
+}
+
+impl DayStatus {
+    /// Problems that keep a day from being solved or benchmarked, with a hint on how to fix them.
+    fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        if self.has_bin {
+            match self.input {
+                FileState::Missing => problems.push(format!(
+                    "input is missing, run `cargo download {}`",
+                    self.day
+                )),
+                FileState::Empty => {
+                    problems.push(format!("input is empty, run `cargo download {}`", self.day))
+                }
+                FileState::Present => {}
+            }
+
+            if self.example == FileState::Empty {
+                problems.push("example is empty".into());
+            }
+        } else if self.timings > 0 || self.answers > 0 {
+            problems.push("solution is missing".into());
+        }
+
+        problems
+    }
+}
+
+fn collect(timings: &Timings, answers: &Answers, config: &Config) -> Vec<DayStatus> {
+    let paths = &config.paths;
+
+    config
+        .event_days()
+        .map(|day| {
+            let timing = timings.data.iter().find(|t| t.day == day);
+            let answer = answers.get(day);
+
+            DayStatus {
+                day,
+                parts: config.parts(day),
+                has_bin: Path::new(&paths.bin_path(day)).exists(),
+                // an encrypted copy is decrypted by `read_file` when the input is missing.
+                input: match FileState::of(&paths.input_path(day)) {
+                    FileState::Missing => FileState::of(&paths.encrypted_input_path(day)),
+                    state => state,
+                },
+                example: FileState::of(&paths.example_path(day)),
+                puzzle: FileState::of(&paths.puzzle_path(day)),
+                timings: timing.map_or(0, |t| {
+                    u8::from(t.part_1.is_some()) + u8::from(t.part_2.is_some())
+                }),
+                answers: answer.map_or(0, |a| {
+                    u8::from(a.part_1.is_some()) + u8::from(a.part_2.is_some())
+                }),
+            }
+        })
+        .collect()
+}
+
+const COLUMNS: [(&str, usize); 7] = [
+    ("Day", 3),
+    ("Bin", 3),
+    ("Input", 5),
+    ("Example", 7),
+    ("Puzzle", 6),
+    ("Timings", 7),
+    ("Answers", 7),
+];
+
+fn construct_table(status: &[DayStatus]) -> String {
+    let mut lines = vec![COLUMNS
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join("  ")];
+
+    for day in status {
+        let problems = day.problems();
+
+        let count = |n: u8| {
+            if n == 0 {
+                "-".to_string()
+            } else {
+                format!("{n}/{}", day.parts)
+            }
+        };
+
+        let cells = [
+            (day.day.to_string(), false),
+            (
+                if day.has_bin { "✓" } else { "✗" }.to_string(),
+                !day.has_bin && (day.timings > 0 || day.answers > 0),
+            ),
+            (
+                day.input.label().to_string(),
+                day.has_bin && day.input != FileState::Present,
+            ),
+            (
+                day.example.label().to_string(),
+                day.has_bin && day.example == FileState::Empty,
+            ),
+            (day.puzzle.label().to_string(), false),
+            (count(day.timings), false),
+            (count(day.answers), false),
+        ];
+
+        // cells are padded before styling, escape codes would throw off the alignment otherwise.
+        let mut line = cells
+            .iter()
+            .zip(COLUMNS)
+            .map(|((cell, problem), (_, width))| {
+                let cell = format!("{cell:^width$}");
+                if *problem {
+                    format!("{ANSI_BOLD}{cell}{ANSI_RESET}")
+                } else {
+                    cell
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        if !problems.is_empty() {
+            line.push_str(&format!(
+                "  {ANSI_BOLD}← {}{ANSI_RESET}",
+                problems.join(", ")
+            ));
+        }
+
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines.join("\n")
+}
+
+impl From<&DayStatus> for JsonValue {
+    fn from(value: &DayStatus) -> Self {
+        JsonValue::Object(HashMap::from([
+            ("day".into(), number(value.day.into_inner())),
+            ("parts".into(), number(value.parts)),
+            ("bin".into(), JsonValue::Boolean(value.has_bin)),
+            ("input".into(), JsonValue::String(value.input.name().into())),
+            (
+                "example".into(),
+                JsonValue::String(value.example.name().into()),
+            ),
+            (
+                "puzzle".into(),
+                JsonValue::String(value.puzzle.name().into()),
+            ),
+            ("timings".into(), number(value.timings)),
+            ("answers".into(), number(value.answers)),
+            (
+                "problems".into(),
+                JsonValue::Array(
+                    value
+                        .problems()
+                        .into_iter()
+                        .map(JsonValue::String)
+                        .collect(),
+                ),
+            ),
+        ]))
+    }
+}
+
+pub fn handle(json: bool) -> Result<(), Error> {
+    let config = Config::read_from_file();
+    let status = collect(
+        &Timings::read_from_file(&config.paths.timings),
+        &Answers::read_from_file(&config.paths.answers),
+        &config,
+    );
+
+    if json {
+        let days = status.iter().map(JsonValue::from).collect();
+        report::print(&JsonValue::Object(HashMap::from([(
+            "days".into(),
+            JsonValue::Array(days),
+        )])));
+        return Ok(());
+    }
+
+    println!("{}", terminal::styled(construct_table(&status)));
+
+    let problems = status.iter().filter(|s| !s.problems().is_empty()).count();
+    if problems > 0 {
+        println!();
+        println!("Found problems with {problems} day(s).");
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{construct_table, DayStatus, FileState};
+    use crate::day;
+    use crate::template::{render::strip_styles, ANSI_BOLD};
+
+    fn status() -> Vec<DayStatus> {
+        vec![
+            DayStatus {
+                day: day!(1),
+                parts: 2,
+                has_bin: true,
+                input: FileState::Present,
+                example: FileState::Present,
+                puzzle: FileState::Present,
+                timings: 2,
+                answers: 1,
+            },
+            DayStatus {
+                day: day!(2),
+                parts: 2,
+                has_bin: true,
+                input: FileState::Empty,
+                example: FileState::Empty,
+                puzzle: FileState::Missing,
+                timings: 0,
+                answers: 0,
+            },
+            DayStatus {
+                day: day!(12),
+                parts: 1,
+                has_bin: false,
+                input: FileState::Missing,
+                example: FileState::Missing,
+                puzzle: FileState::Missing,
+                timings: 0,
+                answers: 1,
+            },
+        ]
+    }
+
+    #[test]
+    fn finds_problems() {
+        let status = status();
+        assert!(status[0].problems().is_empty());
+        assert_eq!(
+            status[1].problems(),
+            vec![
+                "input is empty, run `cargo download 02`",
+                "example is empty"
+            ]
+        );
+        assert_eq!(status[2].problems(), vec!["solution is missing"]);
+    }
+
+    #[test]
+    fn format_status() {
+        let table = construct_table(&status());
+        let expected = [
+            "Day  Bin  Input  Example  Puzzle  Timings  Answers",
+            "01    ✓     ✓       ✓       ✓       2/2      1/2",
+            "02    ✓   empty   empty     ✗        -        -     ← input is empty, run `cargo download 02`, example is empty",
+            "12    ✗     ✗       ✗       ✗        -       1/1    ← solution is missing",
+        ]
+        .join("\n");
+
+        assert_eq!(strip_styles(&table), expected);
+        assert!(!table.lines().nth(1).unwrap().contains(ANSI_BOLD));
+        assert!(table.lines().nth(2).unwrap().contains(ANSI_BOLD));
+    }
+}
@@ -1,40 +1,69 @@
-use std::collections::HashSet;
-
+use crate::template::answers::Answers;
+use crate::template::config::Config;
+use crate::template::report;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{readme_benchmarks, readme_progress, DaySet, Error};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    days: Option<DaySet>,
+    run_all: bool,
+    store: bool,
+    memory: bool,
+    json: bool,
+) -> Result<(), Error> {
+    let config = Config::read_from_file();
+    let stored_timings = Timings::read_from_file(&config.paths.timings);
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+    let days_to_run = match days {
+        Some(days) => days.resolve(
+            &config,
+            &stored_timings,
+            &Answers::read_from_file(&config.paths.answers),
+        ),
+        None if run_all => config.event_days().collect(),
+        // when neither days nor the `--all` flag are given, filter out days that are fully benched.
+        None => config
+            .event_days()
+            .filter(|day| !stored_timings.is_day_complete(*day, config.parts(*day)))
+            .collect(),
+    };
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run = run_multi(&days_to_run, true, true, memory, json)?;
+    let timings = &run.timings;
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let merged_timings = stored_timings.merge(timings);
+        merged_timings
+            .store_file(&config.paths.timings)
+            .map_err(|e| Error::File(config.paths.timings.clone(), e))?;
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+        let progress = readme_progress::collect(
+            &merged_timings,
+            &Answers::read_from_file(&config.paths.answers),
+            &config,
+        );
+
+        // keep stdout free for the JSON document.
+        let info = |msg: &str| {
+            if json {
+                eprintln!("{msg}");
+            } else {
+                println!("{msg}");
             }
-        }
+        };
+
+        info("");
+        readme_benchmarks::update(merged_timings, &progress, &config)?;
+        info("Stored updated benchmarks.");
+
+        readme_progress::update(&progress, &config)?;
+        info("Stored updated progress.");
     }
+
+    if json {
+        report::print(&run.to_json());
+    }
+
+    run.check()
 }
@@ -0,0 +1,92 @@
+use std::{
+    io::{stdout, Write},
+    thread,
+    time::Duration,
+};
+
+use chrono::Utc;
+
+use crate::template::{
+    aoc_cli,
+    commands::{download, read, scaffold},
+    terminal, Day, Error,
+};
+
+/// A freshly unlocked puzzle can take a moment until it is served.
+const DOWNLOAD_ATTEMPTS: u32 = 5;
+const RETRY_DELAY: Duration = Duration::from_secs(3);
+
+/// Scaffolds, downloads and reads the puzzle of the current day.
+/// With `wait`, waits for the next puzzle to unlock first.
+pub fn handle(wait: bool) -> Result<(), Error> {
+    let day = if wait {
+        aoc_cli::check()?;
+        let day = wait_for_unlock()?;
+        download_with_retries(day)?;
+        day
+    } else {
+        let day = Day::today().ok_or(Error::NotToday)?;
+        download::handle(day)?;
+        day
+    };
+
+    scaffold::handle(day, false, None, false)?;
+    read::handle(day, None)
+}
+
+fn wait_for_unlock() -> Result<Day, Error> {
+    let (day, unlock) = Day::next_unlock().ok_or(Error::UnknownUnlock)?;
+
+    println!("Day {} unlocks at {unlock}.", day.into_inner());
+
+    // the countdown is updated in place, which only works in a terminal.
+    let is_interactive = terminal::is_interactive();
+    if !is_interactive {
+        println!("⏳ Waiting...");
+    }
+
+    // `to_std` fails once the remaining duration turns negative.
+    while let Ok(remaining) = (unlock - Utc::now().fixed_offset()).to_std() {
+        if is_interactive {
+            print!("\r⏳ {}", format_countdown(remaining.as_secs()));
+            let _ = stdout().flush();
+        }
+
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    if is_interactive {
+        print!("\r");
+    }
+    println!("🎄 Day {} is unlocked!", day.into_inner());
+    Ok(day)
+}
+
+fn download_with_retries(day: Day) -> Result<(), Error> {
+    let mut attempt = 1;
+
+    loop {
+        match download::download(day) {
+            Err(e) if attempt < DOWNLOAD_ATTEMPTS => {
+                eprintln!(
+                    "Download failed ({e}), retrying in {}s...",
+                    RETRY_DELAY.as_secs()
+                );
+                thread::sleep(RETRY_DELAY);
+                attempt += 1;
+            }
+            result => return Ok(result?),
+        }
+    }
+}
+
+fn format_countdown(secs: u64) -> String {
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let clock = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {clock}")
+    } else {
+        clock
+    }
+}
@@ -0,0 +1,774 @@
+/// Module that reads the optional template configuration from `aoc.toml`.
+/// All commands consult this configuration instead of hard-coding paths and limits.
+/// Only the small subset of TOML the template needs is supported: tables, comments,
+/// strings, integers, booleans and (single-line) arrays of these values.
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    fmt::Display,
+    fs,
+    str::FromStr,
+};
+
+use crate::template::{all_days, days_in_year, Day};
+
+static CONFIG_FILE_PATH: &str = "aoc.toml";
+
+/// Represents the parsed template configuration.
+/// Every setting has a default, so a missing `aoc.toml` is equivalent to an empty one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    /// The event year. Falls back to the `AOC_YEAR` environment variable if not set.
+    pub year: Option<u16>,
+    /// The number of puzzle days. Derived from the event year if not set.
+    pub days: Option<u8>,
+    pub paths: PathsConfig,
+    pub bench: BenchConfig,
+    pub run: RunConfig,
+    pub input: InputConfig,
+    pub download: DownloadConfig,
+    pub session: SessionConfig,
+    pub readme: ReadmeConfig,
+    /// Named module templates that can be selected with `cargo scaffold --template <name>`.
+    pub templates: BTreeMap<String, String>,
+}
+
+impl Config {
+    /// The configured event year, or the value of the `AOC_YEAR` environment variable.
+    pub fn year(&self) -> Option<u16> {
+        self.year
+            .or_else(|| env::var("AOC_YEAR").ok().and_then(|x| x.parse().ok()))
+    }
+
+    /// The number of puzzle days of the configured event.
+    /// Assumes 25 days if neither `days` nor the year are known.
+    pub fn days(&self) -> u8 {
+        self.days
+            .or_else(|| self.year().map(days_in_year))
+            .unwrap_or(25)
+    }
+
+    /// The number of puzzle parts of a day. The last day of an event only has a single part.
+    pub fn parts(&self, day: Day) -> u8 {
+        if day == self.days() {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// An iterator that yields every puzzle day of the configured event.
+    pub fn event_days(&self) -> impl Iterator<Item = Day> {
+        all_days().take(self.days().into())
+    }
+
+    /// Read the configuration file. If not present, returns the default configuration.
+    pub fn read_from_file() -> Self {
+        Self::try_read_from_file()
+            .unwrap_or_else(|e| {
+                eprintln!("{e} Falling back to defaults.");
+                None
+            })
+            .unwrap_or_default()
+    }
+
+    /// Read the configuration file. Returns [`None`] if not present, and an error if it can't be parsed.
+    pub fn try_read_from_file() -> Result<Option<Self>, String> {
+        let Ok(contents) = fs::read_to_string(CONFIG_FILE_PATH) else {
+            return Ok(None);
+        };
+
+        Self::try_from(contents)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse \"{CONFIG_FILE_PATH}\": {e}"))
+    }
+}
+
+impl TryFrom<String> for Config {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let table = parse_document(&value)?;
+        let mut config = Config::default();
+
+        if let Some(year) = table.get_integer("year")? {
+            config.year = Some(
+                u16::try_from(year)
+                    .ok()
+                    .filter(|x| *x >= 2015)
+                    .ok_or("expected `year` to be 2015 or later.")?,
+            );
+        }
+
+        if let Some(days) = table.get_integer("days")? {
+            config.days = Some(
+                u8::try_from(days)
+                    .ok()
+                    .filter(|x| (1..=25).contains(x))
+                    .ok_or("expected `days` to be between 1 and 25.")?,
+            );
+        }
+
+        let paths = &mut config.paths;
+        for (key, value) in [
+            ("paths.inputs", &mut paths.inputs),
+            ("paths.examples", &mut paths.examples),
+            ("paths.puzzles", &mut paths.puzzles),
+            ("paths.bin", &mut paths.bin),
+            ("paths.timings", &mut paths.timings),
+            ("paths.answers", &mut paths.answers),
+            ("paths.checksums", &mut paths.checksums),
+            ("paths.encrypted", &mut paths.encrypted),
+            ("paths.input_key", &mut paths.input_key),
+            ("paths.template", &mut paths.template),
+        ] {
+            if let Some(path) = table.get_string(key)? {
+                *value = path;
+            }
+        }
+
+        let bench = &mut config.bench;
+        for (key, value) in [
+            ("bench.min_samples", &mut bench.min_samples),
+            ("bench.max_samples", &mut bench.max_samples),
+            ("bench.target_millis", &mut bench.target_millis),
+        ] {
+            if let Some(x) = table.get_integer(key)? {
+                *value = u64::try_from(x)
+                    .ok()
+                    .filter(|x| *x > 0)
+                    .ok_or_else(|| format!("expected `{key}` to be a positive integer."))?;
+            }
+        }
+
+        if bench.min_samples > bench.max_samples {
+            return Err(
+                "expected `bench.min_samples` to be less than or equal to `bench.max_samples`."
+                    .into(),
+            );
+        }
+
+        for key in table.keys_with_prefix("templates.") {
+            let name = key.trim_start_matches("templates.").to_string();
+            if let Some(path) = table.get_string(&key)? {
+                config.templates.insert(name, path);
+            }
+        }
+
+        if let Some(extract) = table.get_boolean("download.extract_examples")? {
+            config.download.extract_examples = extract;
+        }
+
+        if let Some(delay) = table.get_integer("download.delay_millis")? {
+            config.download.delay_millis = u64::try_from(delay)
+                .map_err(|_| "expected `download.delay_millis` to be a non-negative integer.")?;
+        }
+
+        if let Some(normalize) = table.get_boolean("input.normalize")? {
+            config.input.normalize = normalize;
+        }
+
+        if let Some(server) = table.get_string("session.server")? {
+            config.session.server = server;
+        }
+
+        if let Some(days) = table.get_integer("session.lifetime_days")? {
+            config.session.lifetime_days = u64::try_from(days)
+                .ok()
+                .filter(|x| *x > 0)
+                .ok_or("expected `session.lifetime_days` to be a positive integer.")?;
+        }
+
+        if let Some(timeout) = table.get_integer("run.timeout_secs")? {
+            config.run.timeout_secs = Some(
+                u64::try_from(timeout)
+                    .ok()
+                    .filter(|x| *x > 0)
+                    .ok_or("expected `run.timeout_secs` to be a positive integer.")?,
+            );
+        }
+
+        if let Some(path) = table.get_string("readme.path")? {
+            config.readme.path = path;
+        }
+
+        if let Some(level) = table.get_integer("readme.heading_level")? {
+            config.readme.heading_level = u8::try_from(level)
+                .ok()
+                .filter(|x| (1..=6).contains(x))
+                .ok_or("expected `readme.heading_level` to be between 1 and 6.")?;
+        }
+
+        if let Some(columns) = table.get_string_array("readme.columns")? {
+            config.readme.columns = columns
+                .iter()
+                .map(|x| x.parse())
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Some(sort) = table.get_string("readme.sort")? {
+            config.readme.sort = sort.parse()?;
+        }
+
+        if let Some(width) = table.get_integer("readme.chart_width")? {
+            config.readme.chart_width = usize::try_from(width)
+                .ok()
+                .filter(|x| *x > 0)
+                .ok_or("expected `readme.chart_width` to be a positive integer.")?;
+        }
+
+        Ok(config)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Locations of the files the template reads and writes, relative to the repository root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathsConfig {
+    pub inputs: String,
+    pub examples: String,
+    pub puzzles: String,
+    /// The directory that contains the solution binaries.
+    pub bin: String,
+    pub timings: String,
+    pub answers: String,
+    /// The checksums of downloaded inputs.
+    pub checksums: String,
+    /// The directory that contains the encrypted inputs written by `cargo inputs encrypt`.
+    pub encrypted: String,
+    /// The file that contains the key of the encrypted inputs. Must not be committed.
+    pub input_key: String,
+    /// The module template used by `cargo scaffold`.
+    pub template: String,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        Self {
+            inputs: "data/inputs".into(),
+            examples: "data/examples".into(),
+            puzzles: "data/puzzles".into(),
+            bin: "src/bin".into(),
+            timings: "data/timings.json".into(),
+            answers: "data/answers.json".into(),
+            checksums: "data/checksums.json".into(),
+            encrypted: "data/encrypted".into(),
+            input_key: ".aoc-input-key".into(),
+            template: "src/template.txt".into(),
+        }
+    }
+}
+
+impl PathsConfig {
+    pub fn input_path(&self, day: Day) -> String {
+        format!("{}/{day}.txt", self.inputs)
+    }
+
+    pub fn example_path(&self, day: Day) -> String {
+        format!("{}/{day}.txt", self.examples)
+    }
+
+    pub fn encrypted_input_path(&self, day: Day) -> String {
+        format!("{}/{day}.txt.enc", self.encrypted)
+    }
+
+    pub fn puzzle_path(&self, day: Day) -> String {
+        format!("{}/{day}.md", self.puzzles)
+    }
+
+    pub fn bin_path(&self, day: Day) -> String {
+        format!("{}/{day}.rs", self.bin)
+    }
+
+    /// Resolves a data folder as passed to [`read_file`](crate::template::read_file).
+    /// `inputs` and `examples` map to their configured directory, other folders are looked up in `data/`.
+    pub fn data_folder(&self, folder: &str) -> String {
+        match folder {
+            "inputs" => self.inputs.clone(),
+            "examples" => self.examples.clone(),
+            "puzzles" => self.puzzles.clone(),
+            x => format!("data/{x}"),
+        }
+    }
+}
+
+/// Settings for benchmarking solutions with `cargo time`.
+/// Each part is executed for approx. `target_millis`, but at least `min_samples` and at most `max_samples` times.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchConfig {
+    pub min_samples: u64,
+    pub max_samples: u64,
+    pub target_millis: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            min_samples: 10,
+            max_samples: 10_000,
+            target_millis: 1000,
+        }
+    }
+}
+
+/// Settings for running solutions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunConfig {
+    /// Solutions that run longer than this are aborted. Does not include build time.
+    pub timeout_secs: Option<u64>,
+}
+
+/// Settings for reading inputs with [`read_file`](crate::template::read_file).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputConfig {
+    /// Normalize line endings and remove trailing whitespace before passing inputs to solutions.
+    pub normalize: bool,
+}
+
+/// Settings for downloading puzzles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DownloadConfig {
+    /// Write example inputs found in the puzzle description to the examples directory.
+    pub extract_examples: bool,
+    /// The pause between requests when downloading several days with `cargo download --all`.
+    pub delay_millis: u64,
+}
+
+impl Default for DownloadConfig {
+    fn default() -> Self {
+        Self {
+            extract_examples: true,
+            delay_millis: 3000,
+        }
+    }
+}
+
+/// Settings for `cargo session`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionConfig {
+    /// The server session cookies are checked against, e.g. a local stub for testing.
+    pub server: String,
+    /// How long a session cookie is valid after it was stored, used to warn before it expires.
+    pub lifetime_days: u64,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            server: "https://adventofcode.com".into(),
+            lifetime_days: 30,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Settings for the benchmark table written by `cargo time --store`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadmeConfig {
+    /// The markdown file that contains the benchmark table markers.
+    pub path: String,
+    /// The level of the `Benchmarks` heading, e.g. `2` for `##`.
+    pub heading_level: u8,
+    /// Columns rendered after the `Part 1` and `Part 2` columns, in order.
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    /// The width of the bars in the [`Column::Chart`] column, in characters.
+    pub chart_width: usize,
+}
+
+impl Default for ReadmeConfig {
+    fn default() -> Self {
+        Self {
+            path: "README.md".into(),
+            heading_level: 2,
+            columns: vec![],
+            sort: SortOrder::Day,
+            chart_width: 20,
+        }
+    }
+}
+
+/// An optional column of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// The number of samples each part was benched with.
+    Samples,
+    /// The time taken by the input parser, if the solution declares one.
+    Parse,
+    /// The number of solved parts.
+    Stars,
+    /// A bar chart of the total runtime relative to the slowest day.
+    Chart,
+    /// The peak heap usage of each part, as measured by `cargo time --memory`.
+    Memory,
+    /// The number of heap allocations of each part, as measured by `cargo time --memory`.
+    Allocations,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "samples" => Ok(Self::Samples),
+            "parse" => Ok(Self::Parse),
+            "stars" => Ok(Self::Stars),
+            "chart" => Ok(Self::Chart),
+            "memory" => Ok(Self::Memory),
+            "allocations" => Ok(Self::Allocations),
+            x => Err(format!(
+                "unknown column `{x}`, expecting one of `samples`, `parse`, `stars`, `chart`, `memory`, `allocations`."
+            )),
+        }
+    }
+}
+
+/// The order of the rows in the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Ascending by day.
+    Day,
+    /// Descending by total runtime, i.e. the slowest day first.
+    Runtime,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "runtime" => Ok(Self::Runtime),
+            x => Err(format!(
+                "unknown sort order `{x}`, expecting `day` or `runtime`."
+            )),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A value in the configuration file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::String(_) => f.write_str("string"),
+            Value::Integer(_) => f.write_str("integer"),
+            Value::Boolean(_) => f.write_str("boolean"),
+            Value::Array(_) => f.write_str("array"),
+        }
+    }
+}
+
+/// A parsed configuration document, keyed by dotted path (e.g. `readme.path`).
+#[derive(Debug, Default)]
+pub struct Table(HashMap<String, Value>);
+
+impl Table {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    pub fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.0
+            .keys()
+            .filter(|k| k.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_string(&self, key: &str) -> Result<Option<String>, String> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s.clone())),
+            Some(x) => Err(format!("expected `{key}` to be a string, found {x}.")),
+        }
+    }
+
+    pub fn get_integer(&self, key: &str) -> Result<Option<i64>, String> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::Integer(i)) => Ok(Some(*i)),
+            Some(x) => Err(format!("expected `{key}` to be an integer, found {x}.")),
+        }
+    }
+
+    pub fn get_boolean(&self, key: &str) -> Result<Option<bool>, String> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::Boolean(b)) => Ok(Some(*b)),
+            Some(x) => Err(format!("expected `{key}` to be a boolean, found {x}.")),
+        }
+    }
+
+    pub fn get_string_array(&self, key: &str) -> Result<Option<Vec<String>>, String> {
+        let err = || format!("expected `{key}` to be an array of strings.");
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::Array(values)) => values
+                .iter()
+                .map(|v| match v {
+                    Value::String(s) => Ok(s.clone()),
+                    _ => Err(err()),
+                })
+                .collect::<Result<_, _>>()
+                .map(Some),
+            Some(_) => Err(err()),
+        }
+    }
+}
+
+fn parse_document(s: &str) -> Result<Table, String> {
+    let mut table = Table::default();
+    let mut prefix = String::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        let err = |msg: &str| format!("line {}: {msg}", i + 1);
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|x| !x.is_empty())
+                .ok_or_else(|| err("malformed table header."))?;
+            prefix = format!("{name}.");
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected `key = value`."))?;
+
+        let key = key.trim().trim_matches('"');
+        if key.is_empty() {
+            return Err(err("missing key."));
+        }
+
+        let value = parse_value(value.trim()).map_err(|e| err(&e))?;
+        table.0.insert(format!("{prefix}{key}"), value);
+    }
+
+    Ok(table)
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(s: &str) -> Result<Value, String> {
+    if let Some(inner) = s.strip_prefix('"') {
+        let inner = inner.strip_suffix('"').ok_or("unterminated string.")?;
+        return Ok(Value::String(
+            inner.replace("\\\"", "\"").replace("\\\\", "\\"),
+        ));
+    }
+
+    if let Some(inner) = s.strip_prefix('[') {
+        let inner = inner.strip_suffix(']').ok_or("unterminated array.")?;
+        return split_array(inner)
+            .into_iter()
+            .map(|x| parse_value(x.trim()))
+            .collect::<Result<_, _>>()
+            .map(Value::Array);
+    }
+
+    match s {
+        "true" => Ok(Value::Boolean(true)),
+        "false" => Ok(Value::Boolean(false)),
+        s => s
+            .replace('_', "")
+            .parse()
+            .map(Value::Integer)
+            .map_err(|_| format!("unsupported value `{s}`.")),
+    }
+}
+
+fn split_array(s: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut in_string = false;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            ',' if !in_string => {
+                items.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    items.push(&s[start..]);
+    // allow trailing commas.
+    items.retain(|x| !x.trim().is_empty());
+    items
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchConfig, Column, Config, PathsConfig, ReadmeConfig, SortOrder};
+    use crate::day;
+
+    #[test]
+    fn handles_empty_config() {
+        let config = Config::try_from(String::new()).unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn parses_readme_config() {
+        let toml = r#"
+            # benchmark table settings
+            [readme]
+            path = "docs/BENCHMARKS.md" # relative to the repo root
+            heading_level = 3
+            columns = ["samples", "stars", "chart",]
+            sort = "runtime"
+            chart_width = 10
+        "#;
+
+        let config = Config::try_from(toml.to_string()).unwrap();
+
+        assert_eq!(
+            config.readme,
+            ReadmeConfig {
+                path: "docs/BENCHMARKS.md".into(),
+                heading_level: 3,
+                columns: vec![Column::Samples, Column::Stars, Column::Chart],
+                sort: SortOrder::Runtime,
+                chart_width: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_paths_and_limits() {
+        let toml = r#"
+            year = 2023
+
+            [paths]
+            inputs = "inputs"
+            bin = "solutions"
+
+            [bench]
+            max_samples = 1_000
+
+            [run]
+            timeout_secs = 30
+
+            [input]
+            normalize = true
+
+            [session]
+            server = "http://localhost:8080"
+
+            [download]
+            extract_examples = false
+        "#;
+
+        let config = Config::try_from(toml.to_string()).unwrap();
+
+        assert_eq!(config.year(), Some(2023));
+        assert_eq!(config.days(), 25);
+        assert_eq!(
+            config.paths,
+            PathsConfig {
+                inputs: "inputs".into(),
+                bin: "solutions".into(),
+                ..PathsConfig::default()
+            }
+        );
+        assert_eq!(config.paths.input_path(day!(1)), "inputs/01.txt");
+        assert_eq!(config.paths.bin_path(day!(12)), "solutions/12.rs");
+        assert_eq!(config.paths.data_folder("examples"), "data/examples");
+        assert_eq!(
+            config.bench,
+            BenchConfig {
+                max_samples: 1000,
+                ..BenchConfig::default()
+            }
+        );
+        assert_eq!(config.run.timeout_secs, Some(30));
+        assert!(config.input.normalize);
+        assert_eq!(config.session.server, "http://localhost:8080");
+        assert_eq!(config.session.lifetime_days, 30);
+        assert!(!config.download.extract_examples);
+    }
+
+    #[test]
+    fn handles_event_days() {
+        let config = Config::try_from("year = 2025".to_string()).unwrap();
+        assert_eq!(config.days(), 12);
+        assert_eq!(config.event_days().last(), Some(day!(12)));
+
+        let config = Config::try_from("year = 2025\ndays = 10".to_string()).unwrap();
+        assert_eq!(config.days(), 10);
+        assert_eq!(config.parts(day!(9)), 2);
+        assert_eq!(config.parts(day!(10)), 1);
+    }
+
+    #[test]
+    fn parses_named_templates() {
+        let toml = "[templates]\ngrid = \"templates/grid.txt\"\nplain = \"templates/plain.txt\"";
+        let config = Config::try_from(toml.to_string()).unwrap();
+        assert_eq!(config.templates.len(), 2);
+        assert_eq!(
+            config.templates.get("grid"),
+            Some(&"templates/grid.txt".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_sample_limits() {
+        let toml = "[bench]\nmin_samples = 100\nmax_samples = 10";
+        Config::try_from(toml.to_string()).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unknown_column() {
+        let toml = "[readme]\ncolumns = [\"foo\"]";
+        Config::try_from(toml.to_string()).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_wrong_value_type() {
+        let toml = "[readme]\nheading_level = \"2\"";
+        Config::try_from(toml.to_string()).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_lines() {
+        let toml = "[readme]\npath";
+        Config::try_from(toml.to_string()).unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+/// Encryption of puzzle inputs, so that they can be committed without publishing them.
+///
+/// Inputs are encrypted with ChaCha20-Poly1305 (RFC 8439), a random nonce and a 256-bit key that is
+/// read from the `AOC_INPUT_KEY` environment variable or from the key file configured in `aoc.toml`.
+/// The authentication tag makes decryption fail if the key does not match or the file was modified.
+use std::{env, fs};
+
+use chacha20poly1305::aead::Aead;
+use chacha20poly1305::{ChaCha20Poly1305, KeyInit, Nonce};
+
+use crate::template::config::Config;
+use crate::template::{write_secret, Day};
+
+const KEY_ENV: &str = "AOC_INPUT_KEY";
+const HEADER: &str = "aoc-encrypted-input v1";
+const LINE_WIDTH: usize = 64;
+const NONCE_LEN: usize = 12;
+
+pub type Key = [u8; 32];
+
+/// Reads the key from `AOC_INPUT_KEY` or the key file. Returns [`None`] if neither is set.
+pub fn read_key(config: &Config) -> Option<Result<Key, String>> {
+    let (source, text) = match env::var(KEY_ENV) {
+        Ok(text) if !text.trim().is_empty() => (format!("`{KEY_ENV}`"), text),
+        _ => {
+            let path = &config.paths.input_key;
+            (format!("\"{path}\""), fs::read_to_string(path).ok()?)
+        }
+    };
+
+    Some(
+        parse_hex(text.trim())
+            .and_then(|bytes| Key::try_from(bytes).ok())
+            .ok_or_else(|| format!("expected the key in {source} to be 64 hex characters.")),
+    )
+}
+
+/// Generates a random key and writes it to the key file.
+pub fn generate_key(config: &Config) -> Result<Key, String> {
+    let mut key = Key::default();
+    getrandom::getrandom(&mut key).map_err(|e| format!("could not generate a key: {e}"))?;
+
+    let path = &config.paths.input_key;
+    write_secret(path, &format!("{}\n", to_hex(&key)))
+        .map_err(|e| format!("could not write key file \"{path}\": {e}"))?;
+
+    Ok(key)
+}
+
+/// Reads the encrypted copy of the input of a day and decrypts it.
+pub fn decrypt_input(day: Day, config: &Config) -> Result<String, String> {
+    let path = config.paths.encrypted_input_path(day);
+    let encrypted =
+        fs::read_to_string(&path).map_err(|e| format!("could not read \"{path}\": {e}"))?;
+
+    let key = read_key(config).ok_or_else(|| {
+        format!(
+            "\"{path}\" can't be decrypted without a key. Set `{KEY_ENV}` or copy the key to \"{}\".",
+            config.paths.input_key
+        )
+    })??;
+
+    decrypt(&encrypted, &key).map_err(|e| format!("could not decrypt \"{path}\": {e}"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Encrypts an input to the text format of encrypted inputs.
+/// Every call uses a new random nonce, so encrypting the same input twice yields different files.
+pub fn encrypt(text: &str, key: &Key) -> Result<String, String> {
+    let mut nonce = [0; NONCE_LEN];
+    getrandom::getrandom(&mut nonce).map_err(|e| format!("could not generate a nonce: {e}"))?;
+
+    let ciphertext = ChaCha20Poly1305::new(key.into())
+        .encrypt(Nonce::from_slice(&nonce), text.as_bytes())
+        .map_err(|_| "the input is too large to be encrypted.")?;
+
+    let mut encrypted = format!("{HEADER}\n");
+    let hex = to_hex(&[nonce.as_slice(), &ciphertext].concat());
+    for line in hex.as_bytes().chunks(LINE_WIDTH) {
+        encrypted.push_str(std::str::from_utf8(line).unwrap());
+        encrypted.push('\n');
+    }
+    Ok(encrypted)
+}
+
+/// Decrypts an encrypted input. Fails if the file is malformed, modified or the key does not match.
+pub fn decrypt(encrypted: &str, key: &Key) -> Result<String, String> {
+    let mut lines = encrypted.lines();
+
+    if lines.next() != Some(HEADER) {
+        return Err("not an encrypted input.".into());
+    }
+
+    let bytes = parse_hex(&lines.collect::<String>())
+        .filter(|bytes| bytes.len() >= NONCE_LEN)
+        .ok_or("malformed encrypted input.")?;
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+
+    ChaCha20Poly1305::new(key.into())
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| "the key does not match, or the file is corrupted.".into())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return None;
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decrypt, encrypt, parse_hex, Key};
+
+    #[test]
+    fn encrypts_inputs() {
+        let key: Key = [7; 32];
+        let text = "1 2\n3 4\n".repeat(20);
+
+        let encrypted = encrypt(&text, &key).unwrap();
+        assert!(encrypted.starts_with("aoc-encrypted-input v1\n"));
+        assert!(!encrypted.contains("1 2"));
+        assert_ne!(encrypted, encrypt(&text, &key).unwrap());
+        assert_eq!(decrypt(&encrypted, &key).unwrap(), text);
+
+        assert!(decrypt(&encrypted, &[8; 32]).is_err());
+        assert!(decrypt("1 2\n3 4\n", &key).is_err());
+        assert_eq!(parse_hex("0aff"), Some(vec![10, 255]));
+        assert_eq!(parse_hex("0g"), None);
+    }
+
+    #[test]
+    fn rejects_modified_inputs() {
+        let key: Key = [7; 32];
+        let encrypted = encrypt("1 2\n", &key).unwrap();
+
+        let (header, hex) = encrypted.split_once('\n').unwrap();
+        let flipped = if hex.ends_with("0\n") { '1' } else { '0' };
+        let modified = format!("{header}\n{}{flipped}\n", &hex[..hex.len() - 2]);
+
+        assert!(decrypt(&modified, &key).is_err());
+        assert!(decrypt(&format!("{header}\n"), &key).is_err());
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
@@ -45,16 +45,42 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a puzzle day of this year's event, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12
+            && today.day() <= days_in_year(u16::try_from(today.year()).ok()?).into()
+        {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
         }
     }
+
+    /// Returns the next day that unlocks and the instant it unlocks at, i.e. midnight server time.
+    /// After the last puzzle day of an event, this is the 1st of december of the next year.
+    pub fn next_unlock() -> Option<(Self, DateTime<FixedOffset>)> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        Self::next_unlock_after(Utc::now().with_timezone(&offset))
+    }
+
+    fn next_unlock_after(now: DateTime<FixedOffset>) -> Option<(Self, DateTime<FixedOffset>)> {
+        let (year, day) = match (now.month(), now.day()) {
+            (12, day) if day < days_in_year(u16::try_from(now.year()).ok()?).into() => {
+                (now.year(), day + 1)
+            }
+            (12, _) => (now.year() + 1, 1),
+            _ => (now.year(), 1),
+        };
+
+        let unlock = now
+            .timezone()
+            .with_ymd_and_hms(year, 12, day, 0, 0, 0)
+            .single()?;
+
+        Some((Self::new(u8::try_from(day).ok()?)?, unlock))
+    }
 }
 
 impl Display for Day {
@@ -100,6 +126,16 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+/// Returns the number of puzzle days of an event.
+/// Events ran for 25 days until 2024, starting with 2025 they run for 12 days.
+pub fn days_in_year(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub fn all_days() -> AllDays {
     AllDays::new()
@@ -187,6 +223,40 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    #[cfg(feature = "today")]
+    fn next_unlock_after() {
+        use chrono::{FixedOffset, TimeZone};
+
+        let server = FixedOffset::east_opt(super::SERVER_UTC_OFFSET * 3600).unwrap();
+        let at = |y, m, d, h| server.with_ymd_and_hms(y, m, d, h, 59, 59).unwrap();
+        let midnight = |y, m, d| server.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap();
+
+        assert_eq!(
+            Day::next_unlock_after(at(2024, 12, 5, 23)),
+            Some((Day(6), midnight(2024, 12, 6)))
+        );
+        assert_eq!(
+            Day::next_unlock_after(at(2024, 11, 30, 23)),
+            Some((Day(1), midnight(2024, 12, 1)))
+        );
+        assert_eq!(
+            Day::next_unlock_after(at(2024, 12, 25, 0)),
+            Some((Day(1), midnight(2025, 12, 1)))
+        );
+        assert_eq!(
+            Day::next_unlock_after(at(2025, 12, 12, 0)),
+            Some((Day(1), midnight(2026, 12, 1)))
+        );
+    }
+
+    #[test]
+    fn days_in_year() {
+        assert_eq!(super::days_in_year(2015), 25);
+        assert_eq!(super::days_in_year(2024), 25);
+        assert_eq!(super::days_in_year(2025), 12);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
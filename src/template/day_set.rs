@@ -0,0 +1,279 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::answers::Answers;
+use crate::template::config::Config;
+use crate::template::readme_progress;
+use crate::template::timings::Timings;
+use crate::template::Day;
+
+/// A single term of a [`DaySet`] expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selector {
+    /// An inclusive range of days. A single day is a range of length one.
+    Range(u8, u8),
+    All,
+    Odd,
+    Even,
+    /// Days where all parts are solved.
+    Solved,
+    /// Days where not all parts are solved.
+    Unsolved,
+    /// The `n` days with the highest stored total runtime.
+    Slowest(usize),
+}
+
+/// A set of days, parsed from a comma-separated list of terms:
+///
+/// - a day, e.g. `5`
+/// - a range of days, e.g. `1..10` (exclusive), `1..=10` (inclusive), `..5` or `20..`
+/// - `all`, `odd` or `even`
+/// - `solved` or `unsolved`, based on known answers and stored timings
+/// - `slowest:<n>`, the `n` days with the highest stored runtime
+///
+/// ```
+/// # use advent_of_code::template::DaySet;
+/// let days: DaySet = "1..=3,odd".parse().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet(Vec<Selector>);
+
+impl DaySet {
+    /// Resolves the expression to the days it selects. Days beyond the event's calendar are never selected.
+    pub fn resolve(&self, config: &Config, timings: &Timings, answers: &Answers) -> HashSet<Day> {
+        let event_days: Vec<Day> = config.event_days().collect();
+        let mut days = HashSet::new();
+
+        for selector in &self.0 {
+            match selector {
+                Selector::Range(start, end) => days.extend(
+                    event_days
+                        .iter()
+                        .filter(|day| (*start..=*end).contains(&day.into_inner())),
+                ),
+                Selector::All => days.extend(&event_days),
+                Selector::Odd => days.extend(event_days.iter().filter(|d| d.into_inner() % 2 == 1)),
+                Selector::Even => {
+                    days.extend(event_days.iter().filter(|d| d.into_inner() % 2 == 0))
+                }
+                Selector::Solved | Selector::Unsolved => {
+                    let solved = *selector == Selector::Solved;
+                    days.extend(
+                        readme_progress::collect(timings, answers, config)
+                            .iter()
+                            .filter(|p| p.is_solved() == solved)
+                            .map(|p| p.day),
+                    );
+                }
+                Selector::Slowest(n) => {
+                    let mut data: Vec<_> = timings
+                        .data
+                        .iter()
+                        .filter(|t| event_days.contains(&t.day))
+                        .collect();
+                    data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+                    days.extend(data.iter().take(*n).map(|t| t.day));
+                }
+            }
+        }
+
+        days
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let selectors = s
+            .split(',')
+            .map(|term| parse_selector(term.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(DaySet(selectors))
+    }
+}
+
+fn parse_selector(term: &str) -> Result<Selector, DaySetFromStrError> {
+    let error = || DaySetFromStrError(term.to_string());
+
+    let parse_day = |s: &str| -> Result<u8, DaySetFromStrError> {
+        Day::from_str(s).map(Day::into_inner).map_err(|_| error())
+    };
+
+    let selector = match term {
+        "all" => Selector::All,
+        "odd" => Selector::Odd,
+        "even" => Selector::Even,
+        "solved" => Selector::Solved,
+        "unsolved" => Selector::Unsolved,
+        _ => {
+            if let Some(n) = term.strip_prefix("slowest:") {
+                Selector::Slowest(n.parse().map_err(|_| error())?)
+            } else if let Some((start, end)) = term.split_once("..") {
+                let start = if start.is_empty() {
+                    1
+                } else {
+                    parse_day(start)?
+                };
+                let end = match end.strip_prefix('=') {
+                    Some(end) => parse_day(end)?,
+                    None if end.is_empty() => 25,
+                    // the exclusive end may be one past the last day, e.g. `20..26`.
+                    None => match end {
+                        "26" => 25,
+                        _ => parse_day(end)?.checked_sub(1).ok_or_else(error)?,
+                    },
+                };
+                if start > end {
+                    return Err(error());
+                }
+                Selector::Range(start, end)
+            } else {
+                let day = parse_day(term)?;
+                Selector::Range(day, day)
+            }
+        }
+    };
+
+    Ok(selector)
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting a day, a range like `1..=10`, \
+            `all`, `odd`, `even`, `solved`, `unsolved` or `slowest:<n>`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySet;
+    use crate::{
+        day,
+        template::{
+            answers::Answers,
+            config::Config,
+            timings::{Timing, Timings},
+            Day, Part,
+        },
+    };
+
+    fn resolve(expr: &str) -> Vec<u8> {
+        let config = Config {
+            days: Some(12),
+            ..Config::default()
+        };
+
+        let timing = |day: Day, total_nanos: f64| Timing {
+            day,
+            part_1: Some("1ms".into()),
+            part_2: Some("1ms".into()),
+            part_1_samples: None,
+            part_2_samples: None,
+            parse: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            total_nanos,
+        };
+
+        let timings = Timings {
+            data: vec![
+                timing(day!(1), 10.0),
+                timing(day!(2), 30.0),
+                timing(day!(3), 20.0),
+            ],
+        };
+
+        let mut answers = Answers::default();
+        answers.insert(day!(4), Part::One, "1");
+
+        let mut days: Vec<u8> = expr
+            .parse::<DaySet>()
+            .unwrap()
+            .resolve(&config, &timings, &answers)
+            .into_iter()
+            .map(Day::into_inner)
+            .collect();
+        days.sort_unstable();
+        days
+    }
+
+    #[test]
+    fn parses_days_and_ranges() {
+        assert_eq!(resolve("5"), vec![5]);
+        assert_eq!(resolve("3,5,7"), vec![3, 5, 7]);
+        assert_eq!(resolve("1..4"), vec![1, 2, 3]);
+        assert_eq!(resolve("1..=4"), vec![1, 2, 3, 4]);
+        assert_eq!(resolve("..3,11.."), vec![1, 2, 11, 12]);
+        assert_eq!(resolve("20..=25"), Vec::<u8>::new());
+        assert_eq!(resolve("10..26"), vec![10, 11, 12]);
+    }
+
+    #[test]
+    fn parses_named_sets() {
+        assert_eq!(resolve("odd"), vec![1, 3, 5, 7, 9, 11]);
+        assert_eq!(resolve("even,1"), vec![1, 2, 4, 6, 8, 10, 12]);
+        assert_eq!(resolve("all").len(), 12);
+        assert_eq!(resolve("solved"), vec![1, 2, 3]);
+        assert_eq!(resolve("unsolved"), (4..=12).collect::<Vec<u8>>());
+        assert_eq!(resolve("slowest:2"), vec![2, 3]);
+    }
+
+    #[test]
+    fn counts_last_day_as_solved() {
+        let config = Config {
+            days: Some(3),
+            ..Config::default()
+        };
+
+        let mut answers = Answers::default();
+        for day in [day!(1), day!(2)] {
+            answers.insert(day, Part::One, "1");
+            answers.insert(day, Part::Two, "2");
+        }
+        answers.insert(day!(3), Part::One, "3");
+
+        let resolve = |expr: &str| {
+            expr.parse::<DaySet>()
+                .unwrap()
+                .resolve(&config, &Timings::default(), &answers)
+                .len()
+        };
+        assert_eq!(resolve("solved"), 3);
+        assert_eq!(resolve("unsolved"), 0);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expr in [
+            "",
+            "0",
+            "26",
+            "5..3",
+            "1..1",
+            "1..27",
+            "slowest:x",
+            "weekends",
+            "1,,2",
+        ] {
+            assert!(expr.parse::<DaySet>().is_err(), "{expr}");
+        }
+    }
+}
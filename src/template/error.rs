@@ -0,0 +1,133 @@
+use std::fmt::Display;
+use std::io;
+
+use crate::template::{
+    aoc_cli::AocCommandError,
+    runner::{EXIT_PANICKED, EXIT_TIMED_OUT, EXIT_WRONG_ANSWER},
+    Day,
+};
+
+/// An error returned by one of the template commands.
+///
+/// Commands never exit the process themselves, the binary decides how errors are reported.
+#[derive(Debug)]
+pub enum Error {
+    /// Calling aoc-cli failed.
+    AocCli(AocCommandError),
+    /// Reading or writing a file, or running a child process failed.
+    Io(io::Error),
+    /// Reading or writing the file at the given path failed.
+    File(String, io::Error),
+    /// The output of a child process could not be captured.
+    BrokenPipe,
+    /// A table in the readme could not be updated.
+    Readme(String),
+    /// The module template could not be read.
+    Template(String),
+    /// Scaffolding failed and all changes were rolled back.
+    Scaffold(String),
+    /// The puzzle description of a day has not been downloaded.
+    PuzzleNotFound(Day, String),
+    /// Part two of a day has not been unlocked.
+    PartNotUnlocked(Day),
+    /// `today` was run outside of the event.
+    NotToday,
+    /// The unlock time of the next puzzle could not be determined.
+    UnknownUnlock,
+    /// The solution of a day failed to compile.
+    BuildFailed(Day),
+    /// The solution of a day exited with a failure, with its exit code.
+    SolutionFailed(Day, Option<i32>),
+    /// Some of the solutions run by `cargo all` or `cargo time` failed.
+    SolutionsFailed(Vec<Day>),
+    /// Some of the days downloaded by `cargo download --all` failed.
+    DownloadsFailed(Vec<Day>),
+    /// Some of the checks run by `cargo doctor` failed.
+    ChecksFailed(usize),
+    /// Encrypting or decrypting inputs failed, e.g. because the key is missing.
+    Encryption(String),
+    /// The session cookie is missing, malformed or was rejected.
+    Session(String),
+}
+
+/// Describes why a solution exited with `code`.
+pub fn describe_exit_code(code: Option<i32>) -> String {
+    match code {
+        Some(EXIT_PANICKED) => "panicked".into(),
+        Some(EXIT_WRONG_ANSWER) => "produced a wrong answer".into(),
+        Some(EXIT_TIMED_OUT) => "timed out".into(),
+        Some(code) => format!("exited with code {code}"),
+        None => "was terminated by a signal".into(),
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) | Error::File(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            Error::Io(e) => write!(f, "{e}"),
+            Error::File(path, e) => write!(f, "could not access \"{path}\": {e}"),
+            Error::BrokenPipe => write!(f, "could not capture the output of a child process."),
+            Error::Readme(e) | Error::Template(e) | Error::Encryption(e) | Error::Session(e) => write!(f, "{e}"),
+            Error::Scaffold(e) => write!(f, "{e} No files were changed."),
+            Error::PuzzleNotFound(day, path) => write!(
+                f,
+                "puzzle description \"{path}\" not found. Run `cargo download {day}` to download it."
+            ),
+            Error::PartNotUnlocked(day) => write!(
+                f,
+                "part two is not unlocked yet. Run `cargo download {day}` after solving part one."
+            ),
+            Error::NotToday => write!(
+                f,
+                "`today` command can only be run between the 1st and the last puzzle day of december. \
+                Please use `scaffold` with a specific day, or `today --wait` to wait for the next puzzle."
+            ),
+            Error::UnknownUnlock => write!(f, "could not determine when the next puzzle unlocks."),
+            Error::BuildFailed(day) => write!(f, "the solution of day {day} failed to build."),
+            Error::SolutionFailed(day, code) => write!(
+                f,
+                "the solution of day {day} {}.",
+                describe_exit_code(*code)
+            ),
+            Error::SolutionsFailed(days) => write!(
+                f,
+                "the solutions of day {} failed.",
+                days.iter()
+                    .map(Day::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Error::DownloadsFailed(days) => write!(
+                f,
+                "the downloads of day {} failed.",
+                days.iter()
+                    .map(Day::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Error::ChecksFailed(count) => write!(f, "{count} of the checks failed."),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::AocCli(e)
+    }
+}
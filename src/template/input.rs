@@ -0,0 +1,184 @@
+/// Integrity checks and normalization of puzzle inputs, applied by [`read_file`](crate::template::read_file).
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+/// The text adventofcode.com returns instead of the input if the session cookie is missing or expired.
+const LOGGED_OUT_TEXT: &str = "Puzzle inputs differ by user";
+
+/// Normalizes line endings to `\n`, removes trailing whitespace of each line and trailing blank lines.
+/// Non-empty text ends with a single newline, like the inputs served by adventofcode.com.
+pub fn normalize(text: &str) -> String {
+    let mut normalized = text
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end_matches('\n')
+        .to_string();
+
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+
+    normalized
+}
+
+/// Returns a description of what looks wrong with an input, e.g. an empty file left by `cargo scaffold`.
+pub fn check(text: &str) -> Option<&'static str> {
+    let trimmed = text.trim_start();
+    let start = trimmed.chars().take(15).collect::<String>().to_lowercase();
+
+    if trimmed.is_empty() {
+        Some("is empty")
+    } else if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        Some("looks like an HTML page")
+    } else if text.contains(LOGGED_OUT_TEXT) {
+        Some("is an error message of adventofcode.com, check your session cookie")
+    } else {
+        None
+    }
+}
+
+/// A 64-bit FNV-1a hash of the text as hex string.
+/// Unlike [`std::hash::DefaultHasher`], it is stable across rust versions.
+pub fn checksum(text: &str) -> String {
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The checksums of inputs at the time they were downloaded, used to detect accidental edits.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Checksums {
+    pub data: HashMap<Day, String>,
+}
+
+impl Checksums {
+    /// Rehydrate checksums from a JSON file. If not present, returns empty checksums.
+    pub fn read_from_file(path: &str) -> Self {
+        fs::read_to_string(path)
+            .map_err(|x| x.to_string())
+            .and_then(Checksums::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Dehydrate checksums to a JSON file.
+    pub fn store_file(&self, path: &str) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Returns whether the input of a day differs from the one that was downloaded.
+    /// Inputs without a stored checksum are never reported as modified.
+    pub fn is_modified(&self, day: Day, text: &str) -> bool {
+        self.data.get(&day).is_some_and(|x| *x != checksum(text))
+    }
+}
+
+impl From<Checksums> for JsonValue {
+    fn from(value: Checksums) -> Self {
+        let mut data: Vec<_> = value.data.into_iter().collect();
+        data.sort_unstable_by_key(|(day, _)| *day);
+
+        let data = data
+            .into_iter()
+            .map(|(day, checksum)| {
+                JsonValue::Object(HashMap::from([
+                    ("day".into(), JsonValue::String(day.to_string())),
+                    ("checksum".into(), JsonValue::String(checksum)),
+                ]))
+            })
+            .collect();
+
+        JsonValue::Object(HashMap::from([("data".into(), JsonValue::Array(data))]))
+    }
+}
+
+impl TryFrom<String> for Checksums {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        let data = json_data
+            .iter()
+            .map(|entry| {
+                let entry = entry
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected checksum to be a JSON object.")?;
+                let day = entry
+                    .get("day")
+                    .and_then(|v| v.get::<String>())
+                    .and_then(|day| Day::from_str(day).ok())
+                    .ok_or("Expected checksum.day to be a Day struct.")?;
+                let checksum = entry
+                    .get("checksum")
+                    .and_then(|v| v.get::<String>())
+                    .ok_or("Expected checksum.checksum to be a string.")?;
+                Ok((day, checksum.clone()))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Checksums { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, checksum, normalize, Checksums};
+    use crate::day;
+
+    #[test]
+    fn normalizes_inputs() {
+        assert_eq!(normalize("1 2  \r\n3\t\r\n\r\n\n"), "1 2\n3\n");
+        assert_eq!(normalize("  indented\n\nblocks"), "  indented\n\nblocks\n");
+        assert_eq!(normalize(" \n\n"), "");
+    }
+
+    #[test]
+    fn checks_inputs() {
+        assert_eq!(check("\n  \n"), Some("is empty"));
+        assert_eq!(
+            check("\n<!DOCTYPE html>\n<html>"),
+            Some("looks like an HTML page")
+        );
+        assert!(
+            check("Puzzle inputs differ by user.  Please log in to get your puzzle input.")
+                .is_some()
+        );
+        assert_eq!(check("1 2\n3 4\n"), None);
+    }
+
+    #[test]
+    fn detects_modified_inputs() {
+        assert_eq!(checksum(""), "cbf29ce484222325");
+        assert_eq!(checksum("a"), "af63dc4c8601ec8c");
+
+        let mut checksums = Checksums::default();
+        checksums.data.insert(day!(1), checksum("1 2\n"));
+
+        let json = tinyjson::JsonValue::from(checksums).stringify().unwrap();
+        let checksums = Checksums::try_from(json).unwrap();
+
+        assert!(!checksums.is_modified(day!(1), "1 2\n"));
+        assert!(checksums.is_modified(day!(1), "1 2\n\n"));
+        assert!(!checksums.is_modified(day!(2), "anything"));
+    }
+}
@@ -1,13 +1,30 @@
-use std::{env, fs};
+use std::{env, fs, path::Path};
 
 pub mod aoc_cli;
 pub mod commands;
 pub mod runner;
+pub mod terminal;
 
 pub use day::*;
+pub use day_set::*;
+pub use error::{describe_exit_code, Error};
+pub use part::*;
 
+use config::Config;
+
+mod answers;
+mod config;
+mod crypto;
 mod day;
+mod day_set;
+mod error;
+mod input;
+mod part;
+mod puzzle;
 mod readme_benchmarks;
+mod readme_progress;
+mod render;
+mod report;
 mod run_multi;
 mod timings;
 
@@ -16,53 +33,145 @@ pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+///
+/// The `inputs` and `examples` folders resolve to the directories configured in `aoc.toml`,
+/// other folders are looked up in `data/`.
+///
+/// Inputs that only exist as encrypted copy, see `cargo inputs`, are decrypted on the fly.
+///
+/// Prints a warning if the file is empty, looks like an error page, or if an input was edited after it was downloaded.
+/// With `input.normalize` in `aoc.toml`, line endings and trailing whitespace are normalized.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_data_file(folder, day, None)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, day: Day, part: Part) -> String {
+    read_data_file(folder, day, Some(part))
+}
+
+fn read_data_file(folder: &str, day: Day, part: Option<Part>) -> String {
+    let config = Config::read_from_file();
+    let file_name = match part {
+        Some(part) => format!("{day}-{part}.txt"),
+        None => format!("{day}.txt"),
+    };
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = cwd.join(config.paths.data_folder(folder)).join(&file_name);
+
+    let is_encrypted = folder == "inputs"
+        && part.is_none()
+        && !filepath.exists()
+        && Path::new(&config.paths.encrypted_input_path(day)).exists();
+
+    let text = if is_encrypted {
+        crypto::decrypt_input(day, &config).unwrap_or_else(|e| panic!("{e}"))
+    } else {
+        fs::read_to_string(filepath).expect("could not open input file")
+    };
+
+    let path = format!("{}/{file_name}", config.paths.data_folder(folder));
+    if let Some(problem) = input::check(&text) {
+        eprintln!("Warning: \"{path}\" {problem}.");
+    }
+
+    if folder == "inputs"
+        && input::Checksums::read_from_file(&config.paths.checksums).is_modified(day, &text)
+    {
+        eprintln!(
+            "Warning: \"{path}\" was modified after it was downloaded. Run `cargo download {day}` to restore it."
+        );
+    }
+
+    if config.input.normalize {
+        input::normalize(&text)
+    } else {
+        text
+    }
+}
+
+/// Writes a file that contains a secret, e.g. a key or the session cookie.
+/// The file is only accessible to its owner from the moment it is created.
+fn write_secret(path: impl AsRef<Path>, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path)?;
+    // the mode only applies to new files, existing ones are restricted before writing.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents.as_bytes())
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternatively, the second parameter can name an input parser (`parse = parse_input`).
+/// The parser is then timed separately and both parts receive a reference to its output.
+///
+/// The last day of an event only has a single part, its `part_two` is never run.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, One] [part_two, Two]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, One]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, Two]);
+    };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, One] [part_two, Two]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+    (@impl $day:expr, $( [$func:expr, $part:ident] )*) => {
+        $crate::solution!(@setup $day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        fn main() {
+            use $crate::template::runner::*;
+            watch_timeout();
+            let input = $crate::template::read_file("inputs", DAY);
+            let parts = part_count(DAY);
+            $( if $crate::template::Part::$part.into_inner() <= parts {
+                run_part($func, &input, DAY, $crate::template::Part::$part);
+            } )*
+            exit_on_wrong_answer();
+        }
+    };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:ident] )*) => {
+        $crate::solution!(@setup $day);
 
         fn main() {
             use $crate::template::runner::*;
+            watch_timeout();
             let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let parsed = run_parse($parse, input.as_str());
+            let parts = part_count(DAY);
+            $( if $crate::template::Part::$part.into_inner() <= parts {
+                run_part($func, &parsed, DAY, $crate::template::Part::$part);
+            } )*
+            exit_on_wrong_answer();
         }
     };
+
+    (@setup $day:expr) => {
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+
+        #[cfg(feature = "dhat-heap")]
+        #[global_allocator]
+        static ALLOC: dhat::Alloc = dhat::Alloc;
+    };
 }
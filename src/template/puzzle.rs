@@ -0,0 +1,230 @@
+//! Helpers for reading the puzzle descriptions that aoc-cli stores in `data/puzzles`.
+
+/// Extracts the puzzle title from a puzzle description, e.g. `Historian Hysteria`
+/// from a heading like `## \--- Day 1: Historian Hysteria ---`.
+pub fn title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let (_, rest) = line.split_once("--- Day ")?;
+        let (_, title) = rest.split_once(':')?;
+        let title = title.trim().trim_end_matches('-').trim();
+        (!title.is_empty()).then(|| title.to_string())
+    })
+}
+
+/// Splits a puzzle description into the sections of part one and (if unlocked) part two.
+pub fn sections(markdown: &str) -> Vec<&str> {
+    match markdown.find("--- Part Two ---") {
+        Some(pos) => {
+            // split at the start of the line that holds the part two heading.
+            let start = markdown[..pos].rfind('\n').map_or(0, |x| x + 1);
+            vec![&markdown[..start], &markdown[start..]]
+        }
+        None => vec![markdown],
+    }
+}
+
+/// Returns the text of `current` that was not part of `previous`,
+/// e.g. the part two section after it was unlocked by solving part one.
+pub fn unlocked_text<'a>(previous: &str, current: &'a str) -> Option<&'a str> {
+    let previous_sections = sections(previous).len();
+    let current_sections = sections(current);
+
+    if current_sections.len() > previous_sections {
+        let start = current.len()
+            - current_sections[previous_sections..]
+                .iter()
+                .map(|x| x.len())
+                .sum::<usize>();
+        return Some(&current[start..]);
+    }
+
+    current
+        .strip_prefix(previous.trim_end())
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+}
+
+/// Finds candidate example inputs, i.e. the contents of all code blocks in order of appearance.
+/// Supports fenced markdown blocks as well as `<pre><code>` blocks. Duplicate blocks are only returned once.
+pub fn example_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks: Vec<(usize, String)> = vec![];
+
+    blocks.extend(
+        find_delimited(markdown, "<pre><code>", "</code></pre>")
+            .into_iter()
+            .map(|(pos, x)| (pos, decode_html(x))),
+    );
+
+    let mut rest = markdown;
+    let mut offset = 0;
+    while let Some(start) = rest.find("```") {
+        // skip the info string of the opening fence, e.g. "```text".
+        let Some(content_start) = rest[start..].find('\n').map(|x| start + x + 1) else {
+            break;
+        };
+        let Some(len) = rest[content_start..].find("```") else {
+            break;
+        };
+        blocks.push((
+            offset + start,
+            rest[content_start..content_start + len].to_string(),
+        ));
+        offset += content_start + len + 3;
+        rest = &rest[content_start + len + 3..];
+    }
+
+    blocks.sort_by_key(|(pos, _)| *pos);
+
+    let mut examples: Vec<String> = vec![];
+    for (_, block) in blocks {
+        let block = block.trim_end_matches('\n').to_string();
+        if !block.trim().is_empty() && !examples.contains(&block) {
+            examples.push(block);
+        }
+    }
+    examples
+}
+
+/// Finds the expected example answer of each part, i.e. the last emphasized code snippet of each section.
+pub fn example_answers(markdown: &str) -> [Option<String>; 2] {
+    let sections = sections(markdown);
+    let answer = |i: usize| {
+        sections
+            .get(i)
+            .and_then(|section| emphasized_code(section).pop())
+    };
+    [answer(0), answer(1)]
+}
+
+/// Emphasized code snippets are how the puzzle text highlights results, e.g. `<code><em>143</em></code>`.
+fn emphasized_code(s: &str) -> Vec<String> {
+    let mut matches: Vec<(usize, &str)> = [
+        ("`*", "*`"),
+        ("*`", "`*"),
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ]
+    .iter()
+    .flat_map(|(open, close)| find_delimited(s, open, close))
+    .filter(|(_, x)| !x.is_empty() && !x.contains('\n'))
+    .collect();
+
+    matches.sort_by_key(|(pos, _)| *pos);
+    matches.into_iter().map(|(_, x)| decode_html(x)).collect()
+}
+
+/// Returns all non-overlapping substrings between `open` and `close`, with the position of `open`.
+fn find_delimited<'a>(s: &'a str, open: &str, close: &str) -> Vec<(usize, &'a str)> {
+    let mut found = vec![];
+    let mut pos = 0;
+
+    while let Some(start) = s[pos..].find(open).map(|x| pos + x) {
+        let content_start = start + open.len();
+        let Some(len) = s[content_start..].find(close) else {
+            break;
+        };
+        found.push((start, &s[content_start..content_start + len]));
+        pos = content_start + len + close.len();
+    }
+
+    found
+}
+
+fn decode_html(s: &str) -> String {
+    let mut text = String::with_capacity(s.len());
+    let mut in_tag = false;
+
+    for c in s.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{example_answers, example_blocks, sections, title, unlocked_text};
+
+    const MARKDOWN: &str = "## \\--- Day 3: Mull It Over ---
+
+For example, consider the following section of corrupted memory:
+
+```
+xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)
+```
+
+Adding up the result of each instruction produces `*161*` (`2*4 + 5*5`).
+
+## \\--- Part Two ---
+
+For example:
+
+```
+xmul(2,4)&mul[3,7]!^don't()_mul(5,5)
+```
+
+This time, the sum of the results is *`48`*.
+";
+
+    #[test]
+    fn extracts_markdown_examples() {
+        assert_eq!(
+            example_blocks(MARKDOWN),
+            vec![
+                "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)".to_string(),
+                "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)".to_string()
+            ]
+        );
+        assert_eq!(
+            example_answers(MARKDOWN),
+            [Some("161".into()), Some("48".into())]
+        );
+    }
+
+    #[test]
+    fn extracts_html_examples() {
+        let html = "<p>For example:</p><pre><code>3   4\n4   3\n<em>2</em>   5\n</code></pre><pre><code>3   4\n4   3\n<em>2</em>   5\n</code></pre><p>Total: <code><em>11</em></code>, not <code>12</code>.</p>";
+        assert_eq!(
+            example_blocks(html),
+            vec!["3   4\n4   3\n2   5".to_string()]
+        );
+        assert_eq!(example_answers(html), [Some("11".into()), None]);
+    }
+
+    #[test]
+    fn finds_unlocked_text() {
+        let part_one = sections(MARKDOWN)[0];
+        let unlocked = unlocked_text(part_one, MARKDOWN).unwrap();
+        assert!(unlocked.starts_with("## \\--- Part Two ---"));
+        assert!(unlocked.ends_with("*`48`*.\n"));
+        assert_eq!(unlocked_text(MARKDOWN, MARKDOWN), None);
+    }
+
+    #[test]
+    fn handles_descriptions_without_examples() {
+        assert!(example_blocks("no examples").is_empty());
+        assert_eq!(example_answers("no examples"), [None, None]);
+    }
+
+    #[test]
+    fn extracts_title() {
+        let md =
+            "## \\--- Day 1: Historian Hysteria ---\n\nThe *Chief Historian* is always present.";
+        assert_eq!(title(md), Some("Historian Hysteria".into()));
+    }
+
+    #[test]
+    fn handles_missing_title() {
+        assert_eq!(title("# Some other file"), None);
+        assert_eq!(title(""), None);
+    }
+}
@@ -1,77 +1,147 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::fs;
 
+use crate::template::config::{Column, Config, PathsConfig, SortOrder};
+use crate::template::readme_progress::DayProgress;
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{Day, Error};
 
-static MARKER: &str = "<!--- benchmarking table --->";
-
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
-}
+pub static MARKER: &str = "<!--- benchmarking table --->";
 
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
+/// Links are relative to the repository root.
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_link_for_bin(paths: &PathsConfig, day: Day) -> String {
+    format!("./{}", paths.bin_path(day).trim_start_matches("./"))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
+        return Err(Error::Readme(
+            "Too many occurences of marker in README.".into(),
         ));
     }
 
     let pos_start = matches
         .first()
         .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+        .ok_or_else(|| Error::Readme("Could not find table start position.".into()))?;
 
     let pos_end = matches
         .last()
         .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+        .ok_or_else(|| Error::Readme("Could not find table end position.".into()))?;
 
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// Replaces the section delimited by `marker` with `table`.
+/// Shared by all tables that are maintained in the readme.
+pub fn replace_table(s: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    s.replace_range(positions.pos_start..positions.pos_end, table);
+    Ok(())
+}
+
+/// Stars are taken from `progress`, so that both tables count them the same way.
+fn construct_table(
+    timings: Timings,
+    progress: &[DayProgress],
+    total_millis: f64,
+    config: &Config,
+) -> String {
+    let paths = &config.paths;
+    let parts = |day| config.parts(day);
+    let config = &config.readme;
+
+    let prefix = "#".repeat(config.heading_level.into());
     let header = format!("{prefix} Benchmarks");
 
+    let mut column_names = String::from("| Day | Part 1 | Part 2 |");
+    let mut column_alignments = String::from("| :---: | :---: | :---:  |");
+
+    for column in &config.columns {
+        column_names.push_str(&format!(" {} |", column_name(*column)));
+        column_alignments.push_str(match column {
+            Column::Chart => " :--- |",
+            _ => " :---: |",
+        });
+    }
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        column_names,
+        column_alignments,
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0_f64, f64::max);
+
+    let mut data = timings.data;
+
+    match config.sort {
+        SortOrder::Day => data.sort_by_key(|t| t.day),
+        SortOrder::Runtime => data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos)),
+    }
+
+    for timing in data {
+        let path = get_link_for_bin(paths, timing.day);
+
+        // the last day of an event has no second part.
+        let part_2 = match (&timing.part_2, parts(timing.day)) {
+            (None, 1) => String::new(),
+            (part_2, _) => format!("`{}`", part_2.as_deref().unwrap_or("-")),
+        };
+
+        let mut line = format!(
+            "| [Day {}]({}) | `{}` | {part_2} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            timing.part_1.as_deref().unwrap_or("-"),
+        );
+
+        for column in &config.columns {
+            let cell = match column {
+                Column::Samples => format!(
+                    "{} / {}",
+                    format_samples(timing.part_1_samples),
+                    format_samples(timing.part_2_samples)
+                ),
+                Column::Parse => format!("`{}`", timing.parse.as_deref().unwrap_or("-")),
+                Column::Stars => "⭐".repeat(
+                    progress
+                        .iter()
+                        .find(|p| p.day == timing.day)
+                        .map_or(0, DayProgress::stars),
+                ),
+                Column::Chart => chart_bar(timing.total_nanos, max_nanos, config.chart_width),
+                Column::Memory => format!(
+                    "{} / {}",
+                    format_memory(timing.part_1_memory.map(|m| m.peak_bytes)),
+                    format_memory(timing.part_2_memory.map(|m| m.peak_bytes))
+                ),
+                Column::Allocations => format!(
+                    "{} / {}",
+                    format_samples(timing.part_1_memory.map(|m| m.allocations)),
+                    format_samples(timing.part_2_memory.map(|m| m.allocations))
+                ),
+            };
+            line.push_str(&format!(" {cell} |"));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
@@ -81,18 +151,65 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+fn column_name(column: Column) -> &'static str {
+    match column {
+        Column::Samples => "Samples",
+        Column::Parse => "Parse",
+        Column::Stars => "Stars",
+        Column::Chart => "Runtime",
+        Column::Memory => "Peak memory",
+        Column::Allocations => "Allocations",
+    }
+}
+
+fn format_samples(samples: Option<u64>) -> String {
+    samples.map_or_else(|| "-".into(), |x| x.to_string())
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_memory(bytes: Option<u64>) -> String {
+    let Some(bytes) = bytes else {
+        return "-".into();
+    };
+
+    match bytes {
+        b if b < 1024 => format!("`{b} B`"),
+        b if b < 1024 * 1024 => format!("`{:.1} KiB`", b as f64 / 1024_f64),
+        b => format!("`{:.1} MiB`", b as f64 / (1024_f64 * 1024_f64)),
+    }
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn chart_bar(nanos: f64, max_nanos: f64, width: usize) -> String {
+    if max_nanos <= 0_f64 || nanos <= 0_f64 {
+        return String::new();
+    }
+
+    // render at least one block so that fast days remain visible.
+    let len = ((nanos / max_nanos) * width as f64).round().max(1_f64) as usize;
+    "█".repeat(len)
+}
+
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    progress: &[DayProgress],
+    total_millis: f64,
+    config: &Config,
+) -> Result<(), Error> {
+    let table = construct_table(timings, progress, total_millis, config);
+    replace_table(s, MARKER, &table)
+}
+
+pub fn update(timings: Timings, progress: &[DayProgress], config: &Config) -> Result<(), Error> {
+    let path = &config.readme.path;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, progress, total_millis, config)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,7 +217,13 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::template::answers::Answers;
+    use crate::template::config::{Column, Config, ReadmeConfig, SortOrder};
+    use crate::template::readme_progress;
+    use crate::{
+        day,
+        template::timings::{MemoryUsage, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,18 +232,33 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,28 +269,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &[], 190.0, &Config::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &[], 190.0, &Config::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &[], 190.0, &Config::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &[], 190.0, &Config::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), &[], 190.0, &Config::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +298,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &[], 190.0, &Config::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +318,80 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_configured_benchmarks() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_samples = Some(100);
+        timings.data[0].part_2_samples = Some(50);
+        timings.data[1].parse = Some("5ms".into());
+        timings.data[2].part_2 = None;
+        timings.data[2].part_1_memory = Some(MemoryUsage {
+            peak_bytes: 1536,
+            allocations: 7,
+        });
+
+        let readme = ReadmeConfig {
+            heading_level: 3,
+            columns: vec![
+                Column::Samples,
+                Column::Parse,
+                Column::Stars,
+                Column::Chart,
+                Column::Memory,
+                Column::Allocations,
+            ],
+            sort: SortOrder::Runtime,
+            chart_width: 9,
+            ..ReadmeConfig::default()
+        };
+
+        let config = Config {
+            readme,
+            ..Config::default()
+        };
+
+        let progress = readme_progress::collect(&timings, &Answers::default(), &config);
+        update_content(&mut s, timings, &progress, 190.0, &config).unwrap();
+        let expected = [
+            "foo",
+            "<!--- benchmarking table --->",
+            "### Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Samples | Parse | Stars | Runtime | Peak memory | Allocations |",
+            "| :---: | :---: | :---:  | :---: | :---: | :---: | :--- | :---: | :---: |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `-` | - / - | `-` | ⭐ | █████████ | `1.5 KiB` / - | 7 / - |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - / - | `5ms` | ⭐⭐ | ███████ | - / - | - / - |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | 100 / 50 | `-` | ⭐⭐ | ███ | - / - | - / - |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn counts_stars_of_last_day() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data.truncate(2);
+        timings.data[1].part_2 = None;
+
+        let config = Config {
+            days: Some(2),
+            readme: ReadmeConfig {
+                columns: vec![Column::Stars],
+                sort: SortOrder::Day,
+                ..ReadmeConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let progress = readme_progress::collect(&timings, &Answers::default(), &config);
+        update_content(&mut s, timings, &progress, 100.0, &config).unwrap();
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` |  | ⭐⭐ |"));
+    }
 }
@@ -0,0 +1,212 @@
+/// Module that updates the readme with a calendar of solved puzzle parts.
+/// Uses the same marker approach as the benchmark table.
+use std::{fs, path::Path};
+
+use crate::template::answers::Answers;
+use crate::template::config::Config;
+use crate::template::readme_benchmarks::{get_link_for_bin, replace_table};
+use crate::template::timings::Timings;
+use crate::template::{Day, Error};
+
+pub static MARKER: &str = "<!--- progress table --->";
+
+/// Represents the progress of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+    pub has_bin: bool,
+    /// The number of puzzle parts, the last day of an event only has one.
+    pub parts: u8,
+}
+
+impl DayProgress {
+    pub fn stars(&self) -> usize {
+        usize::from(self.part_1) + usize::from(self.part_2)
+    }
+
+    /// Whether all puzzle parts of the day are solved.
+    pub fn is_solved(&self) -> bool {
+        self.part_1 && (self.parts < 2 || self.part_2)
+    }
+}
+
+/// Collect the progress of every puzzle day of the event. A part counts as solved if it has a known answer or a stored timing.
+///
+/// The last day only has a single puzzle part, its second star is awarded once all other stars are collected.
+pub fn collect(timings: &Timings, answers: &Answers, config: &Config) -> Vec<DayProgress> {
+    let mut progress: Vec<DayProgress> = config
+        .event_days()
+        .map(|day| {
+            let timing = timings.data.iter().find(|t| t.day == day);
+            let answer = answers.get(day);
+
+            DayProgress {
+                day,
+                part_1: answer.is_some_and(|a| a.part_1.is_some())
+                    || timing.is_some_and(|t| t.part_1.is_some()),
+                part_2: answer.is_some_and(|a| a.part_2.is_some())
+                    || timing.is_some_and(|t| t.part_2.is_some()),
+                has_bin: Path::new(&config.paths.bin_path(day)).exists(),
+                parts: config.parts(day),
+            }
+        })
+        .collect();
+
+    if let Some((last, rest)) = progress.split_last_mut() {
+        if last.parts == 1 {
+            last.part_2 = last.part_1 && rest.iter().all(|p| p.part_1 && p.part_2);
+        }
+    }
+
+    progress
+}
+
+fn construct_table(progress: &[DayProgress], config: &Config) -> String {
+    let prefix = "#".repeat(config.readme.heading_level.into());
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("{prefix} Progress"),
+        String::new(),
+        "| Day | Part 1 | Part 2 | Stars |".into(),
+        "| :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for p in progress {
+        let day = if p.has_bin {
+            format!(
+                "[Day {}]({})",
+                p.day.into_inner(),
+                get_link_for_bin(&config.paths, p.day)
+            )
+        } else {
+            format!("Day {}", p.day.into_inner())
+        };
+
+        lines.push(format!(
+            "| {day} | {} | {} | {} |",
+            if p.part_1 { "⭐" } else { "" },
+            if p.part_2 { "⭐" } else { "" },
+            p.stars()
+        ));
+    }
+
+    let total_stars: usize = progress.iter().map(DayProgress::stars).sum();
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {total_stars} ⭐**"));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &[DayProgress], config: &Config) -> Result<(), Error> {
+    let table = construct_table(progress, config);
+    replace_table(s, MARKER, &table)
+}
+
+pub fn update(progress: &[DayProgress], config: &Config) -> Result<(), Error> {
+    let path = &config.readme.path;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, progress, config)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect, update_content, DayProgress, MARKER};
+    use crate::{
+        day,
+        template::{answers::Answers, config::Config, timings::Timings, Part},
+    };
+
+    fn get_mock_progress() -> Vec<DayProgress> {
+        vec![
+            DayProgress {
+                day: day!(1),
+                part_1: true,
+                part_2: true,
+                has_bin: true,
+                parts: 2,
+            },
+            DayProgress {
+                day: day!(2),
+                part_1: true,
+                part_2: false,
+                has_bin: true,
+                parts: 2,
+            },
+            DayProgress {
+                day: day!(3),
+                part_1: false,
+                part_2: false,
+                has_bin: false,
+                parts: 2,
+            },
+        ]
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_progress(), &Config::default()).unwrap();
+    }
+
+    #[test]
+    fn updates_existing_progress() {
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_progress(), &Config::default()).unwrap();
+        update_content(&mut s, &get_mock_progress(), &Config::default()).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## Progress").count(), 1);
+    }
+
+    #[test]
+    fn awards_final_star() {
+        let config = Config {
+            days: Some(2),
+            ..Config::default()
+        };
+
+        let mut answers = Answers::default();
+        answers.insert(day!(1), Part::One, "1");
+        answers.insert(day!(2), Part::One, "2");
+
+        let progress = collect(&Timings::default(), &answers, &config);
+        assert_eq!(progress.len(), 2);
+        assert_eq!(progress[1].parts, 1);
+        assert!(!progress[1].part_2);
+
+        answers.insert(day!(1), Part::Two, "3");
+        let progress = collect(&Timings::default(), &answers, &config);
+        assert!(progress[1].part_2);
+        assert_eq!(progress.iter().map(DayProgress::stars).sum::<usize>(), 4);
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_progress(), &Config::default()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- progress table --->",
+            "## Progress",
+            "",
+            "| Day | Part 1 | Part 2 | Stars |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | ⭐ | ⭐ | 2 |",
+            "| [Day 2](./src/bin/02.rs) | ⭐ |  | 1 |",
+            "| Day 3 |  |  | 0 |",
+            "",
+            "**Total: 3 ⭐**",
+            "<!--- progress table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,292 @@
+//! Renders the markdown puzzle descriptions stored by aoc-cli for display in a terminal.
+//! Only the subset of markdown that puzzle descriptions use is supported.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const CODE_INDENT: &str = "    ";
+
+/// Renders a puzzle description, wrapping text at `width` columns. Code blocks are never wrapped.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut out: Vec<String> = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            flush_paragraph(&mut out, &mut paragraph, width);
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            out.push(format!("{CODE_INDENT}{line}"));
+            continue;
+        }
+
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            flush_paragraph(&mut out, &mut paragraph, width);
+            push_blank(&mut out);
+        } else if let Some(heading) = heading(trimmed) {
+            flush_paragraph(&mut out, &mut paragraph, width);
+            push_blank(&mut out);
+            out.push(format!(
+                "{ANSI_BOLD}{}{ANSI_RESET}",
+                strip_styles(&render_inline(heading))
+            ));
+            out.push(String::new());
+        } else if let Some(item) = list_item(trimmed) {
+            flush_paragraph(&mut out, &mut paragraph, width);
+            out.extend(wrap(&render_inline(item), width, "  • ", "    "));
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+
+    flush_paragraph(&mut out, &mut paragraph, width);
+
+    while out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+
+    let first = out.iter().position(|x| !x.is_empty()).unwrap_or(out.len());
+    out[first..].join("\n")
+}
+
+fn push_blank(out: &mut Vec<String>) {
+    if out.last().is_some_and(|x| !x.is_empty()) {
+        out.push(String::new());
+    }
+}
+
+fn flush_paragraph(out: &mut Vec<String>, paragraph: &mut Vec<&str>, width: usize) {
+    if paragraph.is_empty() {
+        return;
+    }
+
+    out.extend(wrap(&render_inline(&paragraph.join(" ")), width, "", ""));
+    paragraph.clear();
+}
+
+fn heading(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    (text.len() < line.len() && text.starts_with(' ')).then(|| text.trim())
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    ["* ", "- ", "+ "]
+        .iter()
+        .find_map(|prefix| line.strip_prefix(prefix))
+}
+
+/// Translates inline markdown to ANSI styles: emphasis is shown bold, inline code italic.
+/// Backslash escapes are resolved and links are reduced to their text.
+fn render_inline(s: &str) -> String {
+    let mut text = String::with_capacity(s.len());
+    let mut bold = false;
+    let mut code = false;
+
+    let chars: Vec<char> = s.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                text.push(chars[i + 1]);
+                i += 1;
+            }
+            // inside code, only `*` that directly wrap the code are emphasis, e.g. `*11*` but not `2*4`.
+            '*' if code && !is_code_boundary(&chars, i) => text.push('*'),
+            '*' | '_' if chars[i] == '*' || !code => {
+                // `**strong**` and `*emphasis*` are both highlighted the same way.
+                let marker = chars[i];
+                while chars.get(i + 1) == Some(&marker) {
+                    i += 1;
+                }
+                if marker == '_' && !is_emphasis_boundary(&chars, i) {
+                    text.push('_');
+                } else {
+                    bold = !bold;
+                    text.push_str(&style(bold, code));
+                }
+            }
+            '`' => {
+                code = !code;
+                text.push_str(&style(bold, code));
+            }
+            '[' => match link_text_end(&chars, i) {
+                Some((label_end, link_end)) => {
+                    text.push_str(&render_inline(
+                        &chars[i + 1..label_end].iter().collect::<String>(),
+                    ));
+                    text.push_str(&style(bold, code));
+                    i = link_end;
+                }
+                None => text.push('['),
+            },
+            c => text.push(c),
+        }
+        i += 1;
+    }
+
+    if bold || code {
+        text.push_str(ANSI_RESET);
+    }
+
+    text
+}
+
+/// `_` only starts or ends emphasis at word boundaries, so that e.g. `snake_case` is kept as is.
+fn is_emphasis_boundary(chars: &[char], i: usize) -> bool {
+    let before = i.checked_sub(1).and_then(|x| chars.get(x));
+    let after = chars.get(i + 1);
+    !before.is_some_and(|c| c.is_alphanumeric()) || !after.is_some_and(|c| c.is_alphanumeric())
+}
+
+fn is_code_boundary(chars: &[char], i: usize) -> bool {
+    let before = i.checked_sub(1).and_then(|x| chars.get(x));
+    let after = chars.iter().skip(i + 1).find(|&&c| c != '*');
+    before == Some(&'`') || after.is_none_or(|&c| c == '`')
+}
+
+/// For a link `[label](url)` starting at `start`, returns the positions of `]` and `)`.
+fn link_text_end(chars: &[char], start: usize) -> Option<(usize, usize)> {
+    let label_end = (start..chars.len()).find(|&x| chars[x] == ']')?;
+    if chars.get(label_end + 1) != Some(&'(') {
+        return None;
+    }
+    let link_end = (label_end + 1..chars.len()).find(|&x| chars[x] == ')')?;
+    Some((label_end, link_end))
+}
+
+fn style(bold: bool, code: bool) -> String {
+    let mut s = ANSI_RESET.to_string();
+    if bold {
+        s.push_str(ANSI_BOLD);
+    }
+    if code {
+        s.push_str(ANSI_ITALIC);
+    }
+    s
+}
+
+/// Removes all ANSI escape sequences from a string.
+pub fn strip_styles(s: &str) -> String {
+    let mut text = String::with_capacity(s.len());
+    let mut in_escape = false;
+
+    for c in s.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            c if !in_escape => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn visible_width(s: &str) -> usize {
+    strip_styles(s).chars().count()
+}
+
+/// Greedy word wrap that ignores ANSI escape sequences when measuring words.
+/// Words that are longer than a line are put on a line of their own.
+fn wrap(text: &str, width: usize, first_indent: &str, indent: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    let mut line = first_indent.to_string();
+    let mut line_width = visible_width(first_indent);
+    let mut is_line_empty = true;
+
+    for word in text.split_whitespace() {
+        let word_width = visible_width(word);
+
+        if !is_line_empty && line_width + 1 + word_width > width {
+            lines.push(line);
+            line = indent.to_string();
+            line_width = visible_width(indent);
+            is_line_empty = true;
+        }
+
+        if !is_line_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        line.push_str(word);
+        line_width += word_width;
+        is_line_empty = false;
+    }
+
+    lines.push(line);
+    lines
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, strip_styles};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    const MARKDOWN: &str = "## \\--- Day 1: Historian Hysteria ---
+
+The *Chief Historian* is always present at the big Christmas sleigh launch, but nobody has seen him in months!
+
+For example:
+
+```
+3   4
+4   3
+```
+
+* The first item.
+* The [second](https://example.com) item.
+
+Your answer is `*11*`.
+";
+
+    #[test]
+    fn renders_styles() {
+        let rendered = render(MARKDOWN, 80);
+        assert!(rendered.starts_with(&format!(
+            "{ANSI_BOLD}--- Day 1: Historian Hysteria ---{ANSI_RESET}\n"
+        )));
+        assert!(rendered.contains(&format!("{ANSI_BOLD}Chief Historian{ANSI_RESET}")));
+        assert!(rendered.contains(&format!("{ANSI_BOLD}{ANSI_ITALIC}11")));
+    }
+
+    #[test]
+    fn wraps_text_but_not_code() {
+        let rendered = strip_styles(&render(MARKDOWN, 40));
+        let expected = [
+            "--- Day 1: Historian Hysteria ---",
+            "",
+            "The Chief Historian is always present at",
+            "the big Christmas sleigh launch, but",
+            "nobody has seen him in months!",
+            "",
+            "For example:",
+            "",
+            "    3   4",
+            "    4   3",
+            "",
+            "  • The first item.",
+            "  • The second item.",
+            "",
+            "Your answer is 11.",
+        ]
+        .join("\n");
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn handles_escapes_and_underscores() {
+        assert_eq!(
+            strip_styles(&render(
+                "a \\*literal\\* star, snake_case_name and `2*4 + 5*5`",
+                80
+            )),
+            "a *literal* star, snake_case_name and 2*4 + 5*5"
+        );
+    }
+}
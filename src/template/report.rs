@@ -0,0 +1,275 @@
+/// Machine-readable output of the `--json` flag.
+///
+/// In JSON mode, solution bins print one record per line for their parser and each part instead of the usual output.
+/// The commands collect these records from the output of the bins and combine them into a single JSON document.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{MemoryUsage, Timing};
+use crate::template::{Day, Error, Part};
+
+/// A record printed by a solution bin, i.e. a JSON object with a `type` key.
+pub type Record = HashMap<String, JsonValue>;
+
+/// Prints a JSON value as a single line to stdout.
+pub fn print(value: &JsonValue) {
+    match value.stringify() {
+        Ok(json) => println!("{json}"),
+        Err(e) => eprintln!("Failed to serialize JSON output: {e}"),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub fn number(value: impl Into<u128>) -> JsonValue {
+    JsonValue::Number(value.into() as f64)
+}
+
+pub fn optional(value: Option<JsonValue>) -> JsonValue {
+    value.unwrap_or(JsonValue::Null)
+}
+
+/// The record of a timed run of the parser (`"parse"`) or of a part (`"part"`).
+pub fn timed_record(kind: &str, duration: &Duration, samples: u128) -> Record {
+    Record::from([
+        ("type".into(), JsonValue::String(kind.into())),
+        (
+            "duration".into(),
+            JsonValue::String(format!("{duration:.1?}")),
+        ),
+        ("nanos".into(), number(duration.as_nanos())),
+        ("samples".into(), number(samples)),
+    ])
+}
+
+/// Parses a line of the output of a solution bin. Returns [`None`] for all other output, e.g. debug prints.
+pub fn parse_record(line: &str) -> Option<Record> {
+    if !line.starts_with('{') {
+        return None;
+    }
+
+    let record: Record = line.parse::<JsonValue>().ok()?.try_into().ok()?;
+    record.get("type")?.get::<String>()?;
+    Some(record)
+}
+
+pub fn parse_records(lines: &[String]) -> Vec<Record> {
+    lines.iter().filter_map(|line| parse_record(line)).collect()
+}
+
+fn kind(record: &Record) -> Option<&str> {
+    record.get("type")?.get::<String>().map(String::as_str)
+}
+
+fn part(record: &Record) -> Option<Part> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    record
+        .get("part")?
+        .get::<f64>()
+        .and_then(|x| Part::new(*x as u8))
+}
+
+/// Copies the memory usage measured by a `--memory` run into the matching part records.
+pub fn merge_memory(records: &mut [Record], memory_records: &[Record]) {
+    for memory_record in memory_records {
+        let Some(memory) = memory_record.get("memory") else {
+            continue;
+        };
+
+        if let Some(record) = records
+            .iter_mut()
+            .find(|r| kind(r) == Some("part") && part(r) == part(memory_record))
+        {
+            record.insert("memory".into(), memory.clone());
+        }
+    }
+}
+
+/// Collects the timings of a day from its records. Like in the text output, parts without an answer are not timed.
+pub fn timing_from_records(records: &[Record], day: Day) -> Timing {
+    let mut timing = Timing {
+        day,
+        part_1: None,
+        part_2: None,
+        part_1_samples: None,
+        part_2_samples: None,
+        parse: None,
+        part_1_memory: None,
+        part_2_memory: None,
+        total_nanos: 0_f64,
+    };
+
+    for record in records {
+        let duration = record
+            .get("duration")
+            .and_then(|x| x.get::<String>())
+            .cloned();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = record
+            .get("samples")
+            .and_then(|x| x.get::<f64>())
+            .map(|x| *x as u64);
+        let nanos = record
+            .get("nanos")
+            .and_then(|x| x.get::<f64>())
+            .copied()
+            .unwrap_or_default();
+
+        match kind(record) {
+            Some("parse") => timing.parse = duration,
+            Some("part") if record.get("answer").is_some_and(|x| !x.is_null()) => {
+                let memory = record.get("memory").and_then(parse_memory);
+                match part(record) {
+                    Some(Part::One) => {
+                        timing.part_1 = duration;
+                        timing.part_1_samples = samples;
+                        timing.part_1_memory = memory;
+                    }
+                    Some(Part::Two) => {
+                        timing.part_2 = duration;
+                        timing.part_2_samples = samples;
+                        timing.part_2_memory = memory;
+                    }
+                    None => continue,
+                }
+            }
+            _ => continue,
+        }
+
+        timing.total_nanos += nanos;
+    }
+
+    timing
+}
+
+fn parse_memory(value: &JsonValue) -> Option<MemoryUsage> {
+    let map = value.get::<HashMap<String, JsonValue>>()?;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let get = |key: &str| map.get(key)?.get::<f64>().map(|x| *x as u64);
+
+    Some(MemoryUsage {
+        peak_bytes: get("peak_bytes")?,
+        allocations: get("allocations")?,
+    })
+}
+
+/// How running the solution of a day ended.
+pub enum Outcome {
+    Solved,
+    /// The day has no solution bin.
+    NotSolved,
+    BuildFailed,
+    /// The solution exited with a failure, with its exit code.
+    Failed(Option<i32>),
+}
+
+/// Combines the records of a day into its report.
+pub fn day_report(day: Day, outcome: &Outcome, records: &[Record]) -> JsonValue {
+    let (status, exit_code, error) = match outcome {
+        Outcome::Solved => ("solved", None, None),
+        Outcome::NotSolved => ("not_solved", None, None),
+        Outcome::BuildFailed => ("build_failed", None, Some(Error::BuildFailed(day))),
+        Outcome::Failed(code) => ("failed", *code, Some(Error::SolutionFailed(day, *code))),
+    };
+
+    let without_type = |record: &Record| {
+        let mut record = record.clone();
+        record.remove("type");
+        JsonValue::Object(record)
+    };
+
+    let parse = records
+        .iter()
+        .find(|r| kind(r) == Some("parse"))
+        .map(without_type);
+
+    let parts = records
+        .iter()
+        .filter(|r| kind(r) == Some("part"))
+        .map(without_type)
+        .collect();
+
+    JsonValue::Object(HashMap::from([
+        ("day".into(), number(day.into_inner())),
+        ("status".into(), JsonValue::String(status.into())),
+        (
+            "exit_code".into(),
+            optional(exit_code.map(|x| JsonValue::Number(x.into()))),
+        ),
+        (
+            "error".into(),
+            optional(error.map(|e| JsonValue::String(e.to_string()))),
+        ),
+        ("parse".into(), optional(parse)),
+        ("parts".into(), JsonValue::Array(parts)),
+    ]))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use tinyjson::JsonValue;
+
+    use super::{day_report, merge_memory, parse_records, timing_from_records, Outcome};
+    use crate::day;
+    use crate::template::timings::MemoryUsage;
+
+    fn lines() -> Vec<String> {
+        [
+            r#"{"type":"parse","duration":"1.5ms","nanos":1500000,"samples":500}"#,
+            "debug output",
+            r#"{"type":"part","part":1,"answer":"42","duration":"1.0ms","nanos":1000000,"samples":1000,"correct":true}"#,
+            r#"{"type":"part","part":2,"answer":null,"duration":"2.0ms","nanos":2000000,"samples":500,"correct":null}"#,
+            r#"{"part":1}"#,
+        ]
+        .map(String::from)
+        .to_vec()
+    }
+
+    #[test]
+    fn collects_timings_from_records() {
+        let mut records = parse_records(&lines());
+        assert_eq!(records.len(), 3);
+
+        let memory = parse_records(&[
+            r#"{"type":"part","part":1,"answer":"42","memory":{"peak_bytes":2048,"allocations":12}}"#.into(),
+        ]);
+        merge_memory(&mut records, &memory);
+
+        let timing = timing_from_records(&records, day!(1));
+        assert_eq!(timing.parse.as_deref(), Some("1.5ms"));
+        assert_eq!(timing.part_1.as_deref(), Some("1.0ms"));
+        assert_eq!(timing.part_1_samples, Some(1000));
+        assert_eq!(
+            timing.part_1_memory,
+            Some(MemoryUsage {
+                peak_bytes: 2048,
+                allocations: 12
+            })
+        );
+        assert_eq!(timing.part_2, None);
+        assert_eq!(timing.total_nanos, 2500000_f64);
+    }
+
+    #[test]
+    fn reports_days() {
+        let records = parse_records(&lines());
+        let report = day_report(day!(3), &Outcome::Failed(Some(101)), &records);
+        let report = report.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(report["day"], JsonValue::Number(3.0));
+        assert_eq!(report["status"], JsonValue::String("failed".into()));
+        assert_eq!(report["exit_code"], JsonValue::Number(101.0));
+        assert_eq!(
+            report["error"],
+            JsonValue::String("the solution of day 03 panicked.".into())
+        );
+        assert_eq!(report["parts"].get::<Vec<JsonValue>>().unwrap().len(), 2);
+        assert!(!report["parse"]
+            .get::<HashMap<String, JsonValue>>()
+            .unwrap()
+            .contains_key("type"));
+    }
+}
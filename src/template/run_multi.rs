@@ -1,131 +1,338 @@
-use std::{collections::HashSet, io};
+use std::collections::{HashMap, HashSet};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use tinyjson::JsonValue;
+
+use crate::template::report::{self, number, Outcome};
+use crate::template::terminal::{bold, italic};
+use crate::template::{describe_exit_code, Day, Error};
 
 use super::{
     all_days,
+    config::Config,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// The result of running several solutions.
+pub struct MultiRun {
+    pub timings: Timings,
+    /// Days whose solution failed to build or exited with a failure.
+    pub failed: Vec<Day>,
+    /// The report of every day in `--json` mode.
+    pub reports: Vec<JsonValue>,
+}
+
+impl MultiRun {
+    /// Turns failed solutions into an error.
+    pub fn check(&self) -> Result<(), Error> {
+        if self.failed.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::SolutionsFailed(self.failed.clone()))
+        }
+    }
+
+    /// The JSON document printed by `--json` mode.
+    pub fn to_json(&self) -> JsonValue {
+        JsonValue::Object(HashMap::from([
+            ("days".into(), JsonValue::Array(self.reports.clone())),
+            (
+                "failed".into(),
+                JsonValue::Array(self.failed.iter().map(|d| number(d.into_inner())).collect()),
+            ),
+            (
+                "total_nanos".into(),
+                JsonValue::Number(self.timings.data.iter().map(|t| t.total_nanos).sum()),
+            ),
+        ]))
+    }
+}
+
+/// Runs the solutions of `days_to_run` and collects the timings of successful runs.
+/// In `--json` mode, nothing is printed to stdout, the results are collected into [`MultiRun::reports`] instead.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    is_memory: bool,
+    is_json: bool,
+) -> Result<MultiRun, Error> {
+    let config = Config::read_from_file();
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut failed: Vec<Day> = vec![];
+    let mut reports: Vec<JsonValue> = vec![];
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if !is_json {
             if need_space {
                 println!();
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("{}", bold(format_args!("Day {day}")));
             println!("------");
+        }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = match child_commands::run_solution(day, is_timed, is_release, is_json, &config)
+        {
+            Ok(Some(output)) => output,
+            Ok(None) => {
+                if is_json {
+                    reports.push(report::day_report(day, &Outcome::NotSolved, &[]));
+                } else {
+                    println!("Not solved.");
+                }
+                continue;
+            }
+            Err(Error::BuildFailed(_)) => {
+                if is_json {
+                    reports.push(report::day_report(day, &Outcome::BuildFailed, &[]));
+                } else {
+                    println!("Failed to build.");
+                }
+                failed.push(day);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
 
-            if output.is_empty() {
-                println!("Not solved.");
+        if !output.status.success() {
+            if is_json {
+                let records = report::parse_records(&output.lines);
+                let outcome = Outcome::Failed(output.status.code());
+                reports.push(report::day_report(day, &outcome, &records));
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                println!(
+                    "Failed: solution {}.",
+                    describe_exit_code(output.status.code())
+                );
             }
-        });
+            failed.push(day);
+            continue;
+        }
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+        if is_json {
+            let mut records = report::parse_records(&output.lines);
+
+            if is_memory {
+                if let Some(output) = child_commands::run_memory_profile(day, is_json, &config)? {
+                    report::merge_memory(&mut records, &report::parse_records(&output.lines));
+                }
+            }
+
+            timings.push(report::timing_from_records(&records, day));
+            reports.push(report::day_report(day, &Outcome::Solved, &records));
+            continue;
+        }
+
+        let mut val = child_commands::parse_exec_time(&output.lines, day);
+
+        if is_memory {
+            if let Some(output) = child_commands::run_memory_profile(day, is_json, &config)? {
+                child_commands::parse_memory_usage(&output.lines, &mut val);
+            }
+        }
+
+        timings.push(val);
     }
-}
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
-}
+    let timings = Timings { data: timings };
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+    if is_timed && !is_json {
+        let total_millis = timings.total_millis();
+        println!(
+            "\n{} {}",
+            bold("Total (Run):"),
+            italic(format_args!("{total_millis:.2}ms"))
+        );
     }
-}
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    Ok(MultiRun {
+        timings,
+        failed,
+        reports,
+    })
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{config::Config, report, timings::MemoryUsage, Day, Error};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{Command, ExitStatus, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// The captured stdout and the exit status of a solution bin.
+    pub struct ChildOutput {
+        pub lines: Vec<String>,
+        pub status: ExitStatus,
+    }
+
+    /// Run the solution bin for a given day. Returns `None` if the day has not been scaffolded yet.
+    /// In `--json` mode, only the records of the solution are captured, its other output is forwarded to stderr.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        is_json: bool,
+        config: &Config,
+    ) -> Result<Option<ChildOutput>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&config.paths.bin_path(day)).exists() {
+            return Ok(None);
         }
 
         let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec!["--quiet", "--bin", &day_padded];
 
         if is_release {
             args.push("--release");
         }
 
+        build(day, &args)?;
+
+        // mirror `--time` and `--json` flags to child invocations.
+        args.push("--");
         if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
+        if is_json {
+            args.push("--json");
+        }
+
+        run_cargo(&[&["run"], args.as_slice()].concat(), |line| {
+            if !is_json {
+                return true;
+            }
+            if report::parse_record(line).is_none() {
+                eprintln!("{line}");
+            }
+            false
+        })
+        .map(Some)
+    }
+
+    /// Run the solution bin for a given day under the dhat heap profiler.
+    /// Only the memory statistics of the child are forwarded to stdout, the results were already printed by [`run_solution`].
+    pub fn run_memory_profile(
+        day: Day,
+        is_json: bool,
+        config: &Config,
+    ) -> Result<Option<ChildOutput>, Error> {
+        if !Path::new(&config.paths.bin_path(day)).exists() {
+            return Ok(None);
+        }
+
+        let day_padded = day.to_string();
+        let mut args = vec![
+            "--quiet",
+            "--bin",
+            &day_padded,
+            "--profile",
+            "dhat",
+            "--features",
+            "dhat-heap",
+        ];
+
+        build(day, &args)?;
+        args.extend(["--", "--memory"]);
+        if is_json {
+            args.push("--json");
+        }
+
+        run_cargo(&[&["run"], args.as_slice()].concat(), |line| {
+            !is_json && line.contains(" memory: ")
+        })
+        .map(Some)
+    }
+
+    /// Build a solution bin separately, so that build failures can be told apart from failing solutions.
+    fn build(day: Day, args: &[&str]) -> Result<(), Error> {
+        let status = Command::new("cargo")
+            .arg("build")
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::BuildFailed(day))
+        }
+    }
 
+    fn run_cargo(args: &[&str], forward_line: impl Fn(&str) -> bool) -> Result<ChildOutput, Error> {
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
         let mut cmd = Command::new("cargo")
-            .args(&args)
+            .args(args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let stdout = BufReader::new(cmd.stdout.take().ok_or(Error::BrokenPipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or(Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut lines = vec![];
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                eprintln!("{line}");
             });
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            let line = line?;
+            if forward_line(&line) {
+                println!("{line}");
+            }
+            lines.push(line);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        thread.join().map_err(|_| Error::BrokenPipe)?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok(ChildOutput { lines, status })
+    }
+
+    /// Parse lines like `Part 1 memory: 1024 bytes peak, 3 allocations` into `timing`.
+    pub fn parse_memory_usage(output: &[String], timing: &mut super::Timing) {
+        for line in output {
+            let Some((part, stats)) = line.split_once(" memory: ") else {
+                continue;
+            };
+
+            let Some(usage) = parse_memory_stats(stats) else {
+                eprintln!("Could not parse memory usage from line: {line}");
+                continue;
+            };
+
+            if part.contains("Part 1") {
+                timing.part_1_memory = Some(usage);
+            } else if part.contains("Part 2") {
+                timing.part_2_memory = Some(usage);
+            }
+        }
+    }
+
+    fn parse_memory_stats(s: &str) -> Option<MemoryUsage> {
+        let (peak, allocations) = s.split_once(',')?;
+
+        Some(MemoryUsage {
+            peak_bytes: peak.trim().strip_suffix(" bytes peak")?.parse().ok()?,
+            allocations: allocations
+                .trim()
+                .strip_suffix(" allocations")?
+                .parse()
+                .ok()?,
+        })
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -133,6 +340,11 @@ pub mod child_commands {
             day,
             part_1: None,
             part_2: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            parse: None,
+            part_1_memory: None,
+            part_2_memory: None,
             total_nanos: 0_f64,
         };
 
@@ -149,13 +361,17 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_samples(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, samples)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_samples = samples;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_samples = samples;
+                } else if part.contains("Parse") {
+                    timings.parse = Some(timing_str.into());
                 }
 
                 timings.total_nanos += nanos;
@@ -174,7 +390,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -189,6 +405,16 @@ pub mod child_commands {
         Some((str_timing, parsed_timing))
     }
 
+    fn parse_samples(line: &str) -> Option<u64> {
+        line.split(" samples)")
+            .next()?
+            .split('@')
+            .next_back()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,7 +431,8 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_memory_usage};
+        use crate::template::timings::MemoryUsage;
 
         use crate::day;
 
@@ -222,6 +449,39 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1_samples, Some(100000));
+            assert_eq!(res.part_2_samples, Some(99999));
+        }
+
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: ✔ (1.5ms @ 500 samples)".into(),
+                    "Part 1: 0 (1ms @ 1000 samples)".into(),
+                    "Part 2: 10 (2ms @ 500 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 4500000_f64);
+            assert_eq!(res.parse.unwrap(), "1.5ms");
+            assert_eq!(res.part_1.unwrap(), "1ms");
+        }
+
+        #[test]
+        fn parses_single_samples() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (1.5s @ 1 samples)".into(),
+                    "Part 2: 10 (2.0s @ 1 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 3500000000_f64);
+            assert_eq!(res.part_1.unwrap(), "1.5s");
+            assert_eq!(res.part_2_samples, Some(1));
         }
 
         #[test]
@@ -253,5 +513,27 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_memory_usage() {
+            let mut res = parse_exec_time(&["Part 1: 0 (1ms @ 10 samples)".into()], day!(1));
+            parse_memory_usage(
+                &[
+                    "Part 1: 0 (1.2ms)".into(),
+                    "Part 1 memory: 2048 bytes peak, 12 allocations".into(),
+                    "Part 2: ✖".into(),
+                    "Part 2 memory: garbage".into(),
+                ],
+                &mut res,
+            );
+            assert_eq!(
+                res.part_1_memory,
+                Some(MemoryUsage {
+                    peak_bytes: 2048,
+                    allocations: 12
+                })
+            );
+            assert_eq!(res.part_2_memory, None);
+        }
     }
 }
@@ -1,25 +1,155 @@
 /// Encapsulates code that interacts with solution functions.
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
 use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use tinyjson::JsonValue;
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+use crate::template::answers::Answers;
+use crate::template::commands::download;
+use crate::template::config::{BenchConfig, Config};
+use crate::template::report::{self, number, optional};
+use crate::template::terminal::{bold, is_interactive, italic};
+use crate::template::{aoc_cli, Day, Part};
+
+/// Exit code of a solution that produced an answer which is known to be wrong.
+pub const EXIT_WRONG_ANSWER: i32 = 3;
+/// Exit code of a solution that panicked, this is the default of the rust runtime.
+pub const EXIT_PANICKED: i32 = 101;
+/// Exit code of a solution that exceeded the timeout configured in `aoc.toml`.
+pub const EXIT_TIMED_OUT: i32 = 124;
+
+/// Set once a part produced an answer that differs from a known answer or was rejected on submission.
+static WRONG_ANSWER: AtomicBool = AtomicBool::new(false);
+
+pub fn run_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: Part,
+) {
     let part_str = format!("Part {part}");
+    let is_json = is_json_mode();
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    // intermediate results are overwritten with `\r` once the final result is known.
+    let (result, duration, samples) = run_timed(&func, input, |result| {
+        if !is_json && is_interactive() {
+            print_result(result, &part_str, "");
+        }
+    });
+
+    let result = result.map(|x| x.to_string());
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if !is_json {
+        print_result(&result, &part_str, &format_duration(&duration, samples));
+    }
 
-    if let Some(result) = result {
+    let memory = if is_memory_mode() {
+        measure_memory(&func, input)
+    } else {
+        None
+    };
+
+    if let (Some(stats), false) = (&memory, is_json) {
+        println!(
+            "{part_str} memory: {} bytes peak, {} allocations",
+            stats.peak_bytes, stats.allocations
+        );
+    }
+
+    let mut correct = None;
+    if let Some(result) = &result {
+        correct = verify_result(result, day, part);
         submit_result(result, day, part);
     }
+
+    if is_json {
+        let mut record = report::timed_record("part", &duration, samples);
+        record.insert("part".into(), number(part.into_inner()));
+        record.insert("answer".into(), optional(result.map(JsonValue::String)));
+        record.insert("correct".into(), optional(correct.map(JsonValue::Boolean)));
+        if let Some(stats) = memory {
+            record.insert(
+                "memory".into(),
+                JsonValue::Object(HashMap::from([
+                    ("peak_bytes".into(), number(stats.peak_bytes as u128)),
+                    ("allocations".into(), number(stats.allocations)),
+                ])),
+            );
+        }
+        report::print(&JsonValue::Object(record));
+    }
+}
+
+/// Exit with [`EXIT_WRONG_ANSWER`] if one of the parts produced a wrong answer.
+pub fn exit_on_wrong_answer() {
+    if WRONG_ANSWER.load(Ordering::Relaxed) {
+        process::exit(EXIT_WRONG_ANSWER);
+    }
+}
+
+/// Compare a result with the accepted answer of its part.
+/// Returns whether the result is correct, or [`None`] if the answer is not known.
+fn verify_result(result: &str, day: Day, part: Part) -> Option<bool> {
+    let answers = Answers::read_from_file(&Config::read_from_file().paths.answers);
+
+    let known = answers.get(day).and_then(|answer| match part {
+        Part::One => answer.part_1.as_deref(),
+        Part::Two => answer.part_2.as_deref(),
+    });
+
+    let known = known?;
+    if known != result {
+        eprintln!("Part {part}: ✖ wrong answer, the accepted answer is {known}.");
+        WRONG_ANSWER.store(true, Ordering::Relaxed);
+    }
+
+    Some(known == result)
+}
+
+/// The number of puzzle parts of a day. Parts beyond that are not run.
+pub fn part_count(day: Day) -> u8 {
+    Config::read_from_file().parts(day)
+}
+
+/// Abort the solution if it runs longer than the timeout configured in `aoc.toml`.
+pub fn watch_timeout() {
+    let Some(timeout_secs) = Config::read_from_file().run.timeout_secs else {
+        return;
+    };
+
+    thread::spawn(move || {
+        thread::sleep(Duration::from_secs(timeout_secs));
+        println!();
+        eprintln!("Solution timed out after {timeout_secs}s.");
+        process::exit(EXIT_TIMED_OUT);
+    });
+}
+
+/// Run the input parser of a solution. Like a solution part, the parser is benched in `--time` mode.
+pub fn run_parse<I: Copy, T>(func: impl Fn(I) -> T, input: I) -> T {
+    if is_json_mode() {
+        let (result, duration, samples) = run_timed(func, input, |_| {});
+        let record = report::timed_record("parse", &duration, samples);
+        report::print(&JsonValue::Object(record));
+        return result;
+    }
+
+    let (result, duration, samples) = run_timed(func, input, |_| {
+        if is_interactive() {
+            print!("Parse: ✔");
+        }
+    });
+
+    carriage_return();
+    println!("Parse: ✔{}", format_duration(&duration, samples));
+
+    result
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
@@ -32,8 +162,9 @@ fn run_timed<I: Copy, T>(
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
     let result = {
+        // in memory mode, the heap is profiled separately by `measure_memory`.
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = (!is_memory_mode()).then(dhat::Profiler::new_heap);
 
         func(input)
     };
@@ -41,8 +172,8 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let run = if is_time_mode() {
+        bench(func, input, &base_time, &Config::read_from_file().bench)
     } else {
         (base_time, 1)
     };
@@ -50,14 +181,65 @@ fn run_timed<I: Copy, T>(
     (result, run.0, run.1)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Heap usage of a single execution of a solution part.
+struct MemoryStats {
+    peak_bytes: usize,
+    allocations: u64,
+}
+
+/// `--time` is passed to solutions by `cargo time` and `cargo solve --time`.
+fn is_time_mode() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
+/// `--memory` is passed to solutions by `cargo time --memory`.
+fn is_memory_mode() -> bool {
+    env::args().any(|x| x == "--memory")
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// `--json` is passed to solutions by commands that were called with `--json`.
+/// Results are then printed as one JSON record per line, see [`report`].
+fn is_json_mode() -> bool {
+    env::args().any(|x| x == "--json")
+}
+
+/// Run a solution part once more under a dhat heap profiler and report its peak heap usage.
+/// Returns `None` if the solution was not built with the `dhat-heap` feature.
+#[cfg(feature = "dhat-heap")]
+#[allow(clippy::unnecessary_wraps)]
+fn measure_memory<I: Copy, T>(func: impl Fn(I) -> T, input: I) -> Option<MemoryStats> {
+    // testing mode keeps dhat from printing its summary and writing a report file.
+    let profiler = dhat::Profiler::builder().testing().build();
+    black_box(func(black_box(input)));
+    let stats = dhat::HeapStats::get();
+    drop(profiler);
+
+    Some(MemoryStats {
+        peak_bytes: stats.max_bytes,
+        allocations: stats.total_blocks,
+    })
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn measure_memory<I: Copy, T>(_func: impl Fn(I) -> T, _input: I) -> Option<MemoryStats> {
+    eprintln!("Memory profiling requires the `dhat-heap` feature.");
+    None
+}
+
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> (Duration, u128) {
+    if !is_json_mode() && is_interactive() {
+        print!(" > {}", italic("benching"));
+        let _ = stdout().flush();
+    }
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = (Duration::from_millis(config.target_millis).as_nanos()
+        / cmp::max(base_time.as_nanos(), 10))
+    .clamp(config.min_samples.into(), config.max_samples.into());
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -82,8 +264,9 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
+/// The sample count is always printed in `--time` mode, `cargo time` only stores timings of lines that have one.
 fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
+    if samples == 1 && !is_time_mode() {
         format!(" ({duration:.1?})")
     } else {
         format!(" ({duration:.1?} @ {samples} samples)")
@@ -100,16 +283,16 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
+                    carriage_return();
                     println!("{str}");
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {}{duration_str}", bold(result));
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
+                    carriage_return();
                     println!("{str}");
                 }
             }
@@ -117,21 +300,31 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
         None => {
             if is_intermediate_result {
                 print!("{part}: ✖");
-            } else {
+            } else if is_interactive() {
                 print!("\r");
                 println!("{part}: ✖             ");
+            } else {
+                println!("{part}: ✖");
             }
         }
     }
 }
 
+/// Moves back to the start of the line to overwrite an intermediate result.
+/// Intermediate results are only printed to terminals, see [`is_interactive`].
+fn carriage_return() {
+    if is_interactive() {
+        print!("\r");
+    }
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+fn submit_result(
+    result: &str,
     day: Day,
-    part: u8,
+    part: Part,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
@@ -146,7 +339,7 @@ fn submit_result<T: Display>(
 
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
+    let Ok(part_submit) = args[part_index].parse::<Part>() else {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
     };
@@ -161,5 +354,31 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let output = aoc_cli::submit(day, part, result);
+
+    if let Ok(output) = &output {
+        let response = String::from_utf8_lossy(&output.stdout);
+        if aoc_cli::is_accepted(&response) {
+            handle_accepted_answer(day, part, result);
+        } else if aoc_cli::is_rejected(&response) {
+            WRONG_ANSWER.store(true, Ordering::Relaxed);
+        }
+    }
+
+    Some(output)
+}
+
+/// Stores an accepted answer and fetches the part two description once part one is solved.
+fn handle_accepted_answer(day: Day, part: Part, result: &str) {
+    let config = Config::read_from_file();
+
+    let mut answers = Answers::read_from_file(&config.paths.answers);
+    answers.insert(day, part, result);
+    if let Err(e) = answers.store_file(&config.paths.answers) {
+        eprintln!("Failed to store answer: {e}");
+    }
+
+    if part == Part::One {
+        download::refresh_puzzle(day, &config);
+    }
 }
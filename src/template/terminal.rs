@@ -0,0 +1,139 @@
+/// Detects what the terminal supports, i.e. colors and in-place updates of a line.
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{stdout, IsTerminal};
+use std::str::FromStr;
+
+use crate::template::{render, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Passes the resolved color choice on to child processes, i.e. the solution bins.
+/// Their stdout is captured by `cargo all` and `cargo time`, so they can't detect a terminal themselves.
+const COLOR_ENV: &str = "AOC_COLOR";
+
+/// When to use colors, as passed with `--color`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Use colors if stdout is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn resolve(self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => {
+                env::var_os("NO_COLOR").is_none_or(|x| x.is_empty())
+                    && env::var("TERM").map_or(true, |x| x != "dumb")
+                    && stdout().is_terminal()
+            }
+        }
+    }
+}
+
+impl Display for ColorChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        })
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = ColorChoiceFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(ColorChoiceFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`ColorChoice`].
+#[derive(Debug)]
+pub struct ColorChoiceFromStrError;
+
+impl Error for ColorChoiceFromStrError {}
+
+impl Display for ColorChoiceFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting `auto`, `always` or `never`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Resolves `choice` for this process and all solution bins it runs.
+pub fn init(choice: ColorChoice) {
+    let choice = if choice.resolve() {
+        ColorChoice::Always
+    } else {
+        ColorChoice::Never
+    };
+    env::set_var(COLOR_ENV, choice.to_string());
+}
+
+/// Whether styles should be written to stdout.
+/// Solution bins that were not started by a command detect this themselves.
+pub fn use_color() -> bool {
+    env::var(COLOR_ENV)
+        .ok()
+        .and_then(|x| x.parse::<ColorChoice>().ok())
+        .unwrap_or_default()
+        .resolve()
+}
+
+/// Whether stdout is a terminal, i.e. a line can be updated in place with `\r`.
+/// Otherwise, e.g. when piped into a file, only complete lines are printed.
+pub fn is_interactive() -> bool {
+    stdout().is_terminal()
+}
+
+pub fn bold(text: impl Display) -> String {
+    paint(ANSI_BOLD, text)
+}
+
+pub fn italic(text: impl Display) -> String {
+    paint(ANSI_ITALIC, text)
+}
+
+fn paint(style: &str, text: impl Display) -> String {
+    if use_color() {
+        format!("{style}{text}{ANSI_RESET}")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Removes the styles of pre-rendered text, e.g. a table, if colors are disabled.
+pub fn styled(text: String) -> String {
+    if use_color() {
+        text
+    } else {
+        render::strip_styles(&text)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ColorChoice;
+
+    #[test]
+    fn parses_color_choices() {
+        for choice in [ColorChoice::Auto, ColorChoice::Always, ColorChoice::Never] {
+            assert_eq!(choice.to_string().parse::<ColorChoice>().ok(), Some(choice));
+        }
+        assert!("yes".parse::<ColorChoice>().is_err());
+        assert!(ColorChoice::Always.resolve());
+        assert!(!ColorChoice::Never.resolve());
+    }
+}
@@ -3,17 +3,28 @@ use tinyjson::JsonValue;
 
 use crate::template::Day;
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
-
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_samples: Option<u64>,
+    pub part_2_samples: Option<u64>,
+    /// Time taken by the input parser, if the solution declares one.
+    pub parse: Option<String>,
+    pub part_1_memory: Option<MemoryUsage>,
+    pub part_2_memory: Option<MemoryUsage>,
     pub total_nanos: f64,
 }
 
+/// Represents the heap usage of a single part, as measured by `cargo time --memory`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub peak_bytes: u64,
+    pub allocations: u64,
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -23,15 +34,15 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, path: &str) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(path: &str) -> Self {
+        fs::read_to_string(path)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,7 +62,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -60,10 +71,11 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    /// Checks whether all `parts` of a day have a stored timing.
+    pub fn is_day_complete(&self, day: Day, parts: u8) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == day && t.part_1.is_some() && (parts < 2 || t.part_2.is_some()))
     }
 }
 
@@ -133,10 +145,48 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        insert_optional(
+            &mut map,
+            "part_1_samples",
+            value.part_1_samples.map(to_json_number),
+        );
+        insert_optional(
+            &mut map,
+            "part_2_samples",
+            value.part_2_samples.map(to_json_number),
+        );
+        insert_optional(
+            &mut map,
+            "parse",
+            value.parse.clone().map(JsonValue::String),
+        );
+        insert_optional(
+            &mut map,
+            "part_1_memory",
+            value.part_1_memory.map(JsonValue::from),
+        );
+        insert_optional(
+            &mut map,
+            "part_2_memory",
+            value.part_2_memory.map(JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
 
+#[allow(clippy::cast_precision_loss)]
+fn to_json_number(value: u64) -> JsonValue {
+    JsonValue::Number(value as f64)
+}
+
+/// Optional keys are omitted when not set so that timings stay readable by older template versions.
+fn insert_optional(map: &mut HashMap<String, JsonValue>, key: &str, value: Option<JsonValue>) {
+    if let Some(value) = value {
+        map.insert(key.into(), value);
+    }
+}
+
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
@@ -166,15 +216,85 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // optional keys that were added in later versions of the template.
+        let part_1_samples = get_optional_samples(json, "part_1_samples")?;
+        let part_2_samples = get_optional_samples(json, "part_2_samples")?;
+
+        let parse = match json.get("parse") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+        };
+
+        let part_1_memory = get_optional_memory(json, "part_1_memory")?;
+        let part_2_memory = get_optional_memory(json, "part_2_memory")?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_samples,
+            part_2_samples,
+            parse,
+            part_1_memory,
+            part_2_memory,
             total_nanos,
         })
     }
 }
 
+impl From<MemoryUsage> for JsonValue {
+    fn from(value: MemoryUsage) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("peak_bytes".into(), to_json_number(value.peak_bytes));
+        map.insert("allocations".into(), to_json_number(value.allocations));
+        JsonValue::Object(map)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn get_optional_samples(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<u64>, String> {
+    match json.get(key) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(v) => v
+            .get::<f64>()
+            .map(|x| Some(*x as u64))
+            .ok_or_else(|| format!("Expected timing.{key} to be null or a number.")),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn get_optional_memory(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<MemoryUsage>, String> {
+    let err = || format!("Expected timing.{key} to be null or a memory usage object.");
+
+    let memory = match json.get(key) {
+        None | Some(JsonValue::Null) => return Ok(None),
+        Some(v) => v.get::<HashMap<String, JsonValue>>().ok_or_else(err)?,
+    };
+
+    let get_number = |k: &str| {
+        memory
+            .get(k)
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or_else(err)
+    };
+
+    Ok(Some(MemoryUsage {
+        peak_bytes: get_number("peak_bytes")?,
+        allocations: get_number("allocations")?,
+    }))
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
@@ -190,18 +310,33 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -209,7 +344,10 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{MemoryUsage, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -223,6 +361,24 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_optional_keys() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_1_samples": 100, "parse": "1ms", "part_2_memory": { "peak_bytes": 2048, "allocations": 3 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_samples, Some(100));
+            assert_eq!(timing.part_2_samples, None);
+            assert_eq!(timing.parse, Some("1ms".to_string()));
+            assert_eq!(timing.part_1_memory, None);
+            assert_eq!(
+                timing.part_2_memory,
+                Some(MemoryUsage {
+                    peak_bytes: 2048,
+                    allocations: 3
+                })
+            );
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -247,6 +403,7 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{MemoryUsage, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,6 +423,28 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn round_trips_memory_usage() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_1_memory = Some(MemoryUsage {
+                peak_bytes: 1536,
+                allocations: 7,
+            });
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+
+            assert_eq!(
+                timing.part_1_memory,
+                Some(MemoryUsage {
+                    peak_bytes: 1536,
+                    allocations: 7
+                })
+            );
+            assert_eq!(timing.part_2_memory, None);
+        }
     }
 
     mod is_day_complete {
@@ -281,11 +460,16 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1), 2), true);
         }
 
         #[test]
@@ -295,11 +479,17 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1), 2), false);
+            assert_eq!(timings.is_day_complete(day!(1), 1), true);
         }
 
         #[test]
@@ -309,11 +499,16 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1), 2), false);
         }
     }
 
@@ -333,6 +528,11 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -353,6 +553,11 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    parse: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 0_f64,
                 }],
             };